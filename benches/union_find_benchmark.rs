@@ -0,0 +1,94 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use mimalloc::MiMalloc;
+use nanorand::{Rng, WyRand};
+use sc2001::union_find::{
+    PathHalving, PathSplitting, QuickFind, QuickUnion, UnionByRank, UnionFind, WQupc,
+    WeightedQuickUnion,
+};
+
+#[global_allocator]
+static GLOBAL: MiMalloc = MiMalloc;
+
+/// `n` random `(union, p, q)` operations, half unions and half connected
+/// queries, so every strategy sees the same sequence
+fn gen_ops(seed: u64, n: usize) -> Vec<(bool, usize, usize)> {
+    let mut rng = WyRand::new_seed(seed);
+    (0..n)
+        .map(|_| {
+            (
+                rng.generate::<bool>(),
+                rng.generate_range(0..n),
+                rng.generate_range(0..n),
+            )
+        })
+        .collect()
+}
+
+/// every strategy has its own inherent `union` and `connected` so a macro
+/// is used instead of a generic function
+macro_rules! bench_uf {
+    ($c:expr, $name:expr, $uf:ty, $n:expr, $ops:expr) => {
+        $c.bench_function($name, |b| {
+            b.iter(|| {
+                let mut uf = <$uf>::new($n);
+                let mut connected = 0;
+                for &(is_union, p, q) in $ops {
+                    if is_union {
+                        uf.union(p, q);
+                    } else if uf.connected(p, q) {
+                        connected += 1;
+                    }
+                }
+                connected
+            })
+        });
+    };
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    for n in [1_000, 10_000, 100_000] {
+        let ops = gen_ops(42069, n);
+
+        // quick find is O(n) per union, too slow past this
+        if n <= 10_000 {
+            bench_uf!(c, &format!("quick_find(n_{})", n), UnionFind<QuickFind, usize>, n, &ops);
+        }
+        bench_uf!(c, &format!("quick_union(n_{})", n), UnionFind<QuickUnion, usize>, n, &ops);
+        bench_uf!(
+            c,
+            &format!("weighted_quick_union(n_{})", n),
+            UnionFind<WeightedQuickUnion, usize, Vec<usize>>,
+            n,
+            &ops
+        );
+        bench_uf!(
+            c,
+            &format!("union_by_rank(n_{})", n),
+            UnionFind<UnionByRank, usize, Vec<usize>>,
+            n,
+            &ops
+        );
+        bench_uf!(c, &format!("wqupc(n_{})", n), UnionFind<WQupc, usize, Vec<usize>>, n, &ops);
+        bench_uf!(
+            c,
+            &format!("wqupc_path_halving(n_{})", n),
+            UnionFind<WQupc<PathHalving>, usize, Vec<usize>>,
+            n,
+            &ops
+        );
+        bench_uf!(
+            c,
+            &format!("wqupc_path_splitting(n_{})", n),
+            UnionFind<WQupc<PathSplitting>, usize, Vec<usize>>,
+            n,
+            &ops
+        );
+    }
+}
+
+criterion_group!{
+    name = benches;
+    config = Criterion::default().significance_level(0.05).sample_size(30);
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
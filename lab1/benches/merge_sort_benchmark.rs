@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use mimalloc::MiMalloc;
+use nanorand::{Rng, WyRand};
+use sc2001::merge_sort::MergeSort;
+
+#[global_allocator]
+static GLOBAL: MiMalloc = MiMalloc;
+
+type SortFn = fn(&mut [u32]);
+
+fn gen_random_array(seed: u64, n: usize) -> Vec<u32> {
+    let mut rng = WyRand::new_seed(seed);
+    (0..n).map(|_| rng.generate()).collect()
+}
+
+/// merge_sort_copying is the original sort that clones into a new `T` buffer
+/// on every merge. The other three move elements by swapping along an index
+/// permutation, merge_sort allocates that permutation on every merge while
+/// prealloc and bottom_up allocate it once for the whole sort.
+fn criterion_benchmark(c: &mut Criterion) {
+    for (name, n) in [("1k", 1_000), ("10k", 10_000), ("100k", 100_000), ("1mill", 1_000_000)] {
+        let data = gen_random_array(420, n);
+        let sorts: [(&str, SortFn); 4] = [
+            ("merge_sort_copying", MergeSort::sort_copying),
+            ("merge_sort", MergeSort::sort),
+            ("merge_sort_prealloc", MergeSort::sort_prealloc),
+            ("merge_sort_bottom_up", MergeSort::sort_bottom_up),
+        ];
+
+        for (sort_name, sort) in sorts {
+            c.bench_function(&format!("{}({})", sort_name, name), |b| {
+                b.iter_batched(|| data.clone(), |mut data| sort(&mut data), BatchSize::LargeInput)
+            });
+        }
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().significance_level(0.05).sample_size(30);
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
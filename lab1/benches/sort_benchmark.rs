@@ -1,10 +1,10 @@
-use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+#[cfg(not(feature = "key_cmp"))]
+use criterion::BatchSize;
+use criterion::{criterion_group, criterion_main, Criterion};
 use mimalloc::MiMalloc;
 use nanorand::{Rng, WyRand};
-use sc2001::{
-    heap_sort::HeapSort, insertion_merge::InsertionMergeSort, merge_sort::MergeSort,
-    quicksort::QuickSort,
-};
+#[cfg(not(feature = "key_cmp"))]
+use sc2001::{insertion_merge::InsertionMergeSort, sorter::sorters};
 
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
@@ -22,95 +22,34 @@ where
     (0..N).map(|_| rng.generate()).collect()
 }
 
+// the benches below are compiled out with `key_cmp`
+#[cfg_attr(feature = "key_cmp", allow(unused_variables))]
 fn criterion_benchmark(c: &mut Criterion) {
+    #[cfg(not(feature = "key_cmp"))]
     let rand_array_1k = gen_random_array::<1000, _>(420);
+    #[cfg(not(feature = "key_cmp"))]
     let rand_array_10k = gen_random_array::<1_0000, _>(421);
+    #[cfg(not(feature = "key_cmp"))]
     let rand_array_100k = gen_random_array::<1_00000, _>(422);
+    #[cfg(not(feature = "key_cmp"))]
     let rand_array_500k = gen_random_array::<5_00000, _>(422);
+    #[cfg(not(feature = "key_cmp"))]
     let rand_array_1mill = gen_random_array::<1_000_000, _>(423);
-    let rand_array_10mill = gen_random_array::<10_000_000, _>(424);
-
-    // let aux_buf = Vec::with_capacity(100000);
-
-    // c.bench_function("merge_sort 1000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_1th.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("merge_sort 10000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10th.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("quick_sort 10000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10th.clone(),
-    //         |mut data| QuickSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("heap_sort 10000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10th.clone(),
-    //         |mut data| HeapSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("merge_sort 100000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_100th.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("insertion_merge_sort 100000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_1mill.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("heap_sort 100000", |b| {
-    //     b.iter_batched(
-    //         || rand_array_100th.clone(),
-    //         |mut data| HeapSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
 
-    // c.bench_function("quick_sort 1mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_1mill.clone(),
-    //         |mut data| QuickSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("heap_sort 1mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_1mill.clone(),
-    //         |mut data| HeapSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("merge_sort 1mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_1mill.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
+    // insertion sort is quadratic so every sort is only compared on the
+    // smaller arrays
+    #[cfg(not(feature = "key_cmp"))]
+    for (name, data) in [("1k", &rand_array_1k), ("10k", &rand_array_10k)] {
+        for sorter in sorters::<u32>(20) {
+            c.bench_function(&format!("{}({})", sorter.name(), name), |b| {
+                b.iter_batched(
+                    || data.clone(),
+                    |mut data| sorter.sort(&mut data),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
 
     // for sz in 3..=512 {
     //     c.bench_function(&format!("insertion_merge_sort(1mill_s{})", sz), |b| {
@@ -176,72 +115,6 @@ fn criterion_benchmark(c: &mut Criterion) {
             )
         });
     }
-
-    // c.bench_function("merge_sort(10mill_s20)", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // #[cfg(not(feature = "key_cmp"))]
-    // c.bench_function("insertion_merge_sort(10mill_s20)", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| InsertionMergeSort::sort(&mut data, 20),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("insertion_merge_sort(std_lib)", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| data.sort(),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-
-    // c.bench_function("insertion_merge_sort 10mill S = 65", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| InsertionMergeSort::<65>::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("merge_sort 10mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| MergeSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("quicksort 10mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| QuickSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("heap_sort 10mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| HeapSort::sort(&mut data),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
-
-    // c.bench_function("pdqsort_std 10mill", |b| {
-    //     b.iter_batched(
-    //         || rand_array_10mill.clone(),
-    //         |mut data| data.sort_unstable(),
-    //         BatchSize::LargeInput,
-    //     )
-    // });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -110,6 +110,6 @@ mod test {
             ));
         }
 
-        f.write(string.as_bytes()).unwrap();
+        f.write_all(string.as_bytes()).unwrap();
     }
 }
@@ -1,14 +1,78 @@
+use std::collections::BinaryHeap;
+
 use criterion::{criterion_group, criterion_main, Criterion};
 use lab2::{
-    djikstra_bheap_list_graph, gen_graph,
-    graph::{ListGraph, MatrixGraph}, assert_graph_edge, djikstra_bheap_matrix, djikstra_array_pq_list_graph, djikstra_array_pq_matrix,
+    array_pq::ArrayPriorityQueue,
+    assert_graph_edge,
+    fib_heap::FibonacciHeap,
+    gen_graph,
+    graph::ListGraph,
+    pairing_heap::PairingHeap,
+    point_to_point::{a_star, bidirectional_djikstra, djikstra_early_exit, gen_grid_graph, manhattan},
+    radix_heap::RadixHeap,
 };
 use mimalloc::MiMalloc;
-use nanorand::{Rng, WyRand};
+use sc2001::{
+    all_pairs::{floyd_warshall, johnson},
+    boruvka::{boruvka, boruvka_parallel},
+    djikstra::{djikstra_eager_with, djikstra_with},
+    graph::{Edge, WeightedGraph},
+    indexed_heap::IndexedMinHeap,
+    kruskal::kruskal,
+    prims::{prims, prims_eager_with},
+    priority_queue::{IndexedPriorityQueue, PriorityQueue},
+    test_utils::gen_connected_graph,
+};
 
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 
+/// Benchmarks Djikstra from vertex 0 using the priority queue `Q`, adding a
+/// new queue variant only needs another call to this
+fn bench_djikstra<Q, G>(c: &mut Criterion, name: &str, graph: &G)
+where
+    Q: PriorityQueue<Edge<u32>> + Default,
+    G: WeightedGraph<u32> + Clone,
+{
+    c.bench_function(name, |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |graph| djikstra_with::<Q, _, _>(&graph, 0),
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
+
+/// Same as `bench_djikstra` for the decrease key version with the indexed
+/// priority queue `Q`
+fn bench_djikstra_eager<Q, G>(c: &mut Criterion, name: &str, graph: &G)
+where
+    Q: IndexedPriorityQueue<u32>,
+    G: WeightedGraph<u32> + Clone,
+{
+    c.bench_function(name, |b| {
+        b.iter_batched(
+            || graph.clone(),
+            |graph| djikstra_eager_with::<Q, _, _>(&graph, 0),
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
+
+/// Benchmarks eager Prim's from vertex 0 using the indexed priority queue `Q`
+fn bench_prims_eager<Q>(c: &mut Criterion, name: &str, graph: &ListGraph)
+where
+    Q: IndexedPriorityQueue<u32>,
+{
+    c.bench_function(name, |b| {
+        b.iter_batched(
+            || graph.0.clone(),
+            |graph| prims_eager_with::<Q>(graph, 0),
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     // 9900 because need minus v
     for e in (100..=9900).step_by(100) {
@@ -17,100 +81,191 @@ fn criterion_benchmark(c: &mut Criterion) {
         let list_graph = ListGraph::from(matrix_graph.clone());
         assert_graph_edge(&matrix_graph, e);
 
-        c.bench_function(
+        bench_djikstra::<BinaryHeap<_>, _>(
+            c,
             &format!("djikstra_bheap_list_graph(e_{},v_{})", e, v),
-            |b| {
-                b.iter_batched(
-                    || list_graph.clone(),
-                    |graph| djikstra_bheap_list_graph(graph, 0),
-                    criterion::BatchSize::SmallInput
-                );
-            },
+            &list_graph.0,
+        );
+        bench_djikstra::<BinaryHeap<_>, _>(
+            c,
+            &format!("djikstra_bheap_matrix_graph(e_{},v_{})", e, v),
+            &matrix_graph.0,
+        );
+        bench_djikstra::<ArrayPriorityQueue<_>, _>(
+            c,
+            &format!("djikstra_array_pq_list_graph(e_{},v_{})", e, v),
+            &list_graph.0,
+        );
+        bench_djikstra::<ArrayPriorityQueue<_>, _>(
+            c,
+            &format!("djikstra_array_pq_matrix_graph(e_{},v_{})", e, v),
+            &matrix_graph.0,
+        );
+        bench_djikstra::<RadixHeap, _>(
+            c,
+            &format!("djikstra_radix_heap_list_graph(e_{},v_{})", e, v),
+            &list_graph.0,
+        );
+        bench_djikstra::<RadixHeap, _>(
+            c,
+            &format!("djikstra_radix_heap_matrix_graph(e_{},v_{})", e, v),
+            &matrix_graph.0,
+        );
+
+        // eager (decrease key) vs lazy (binary heap) on the same inputs
+        bench_djikstra_eager::<IndexedMinHeap<_>, _>(
+            c,
+            &format!("djikstra_eager_list_graph(e_{},v_{})", e, v),
+            &list_graph.0,
+        );
+        bench_djikstra_eager::<IndexedMinHeap<_>, _>(
+            c,
+            &format!("djikstra_eager_matrix_graph(e_{},v_{})", e, v),
+            &matrix_graph.0,
+        );
+        bench_djikstra_eager::<FibonacciHeap<_>, _>(
+            c,
+            &format!("djikstra_fib_heap_list_graph(e_{},v_{})", e, v),
+            &list_graph.0,
+        );
+        bench_djikstra_eager::<PairingHeap<_>, _>(
+            c,
+            &format!("djikstra_pairing_heap_list_graph(e_{},v_{})", e, v),
+            &list_graph.0,
         );
 
+        // all pairs on the same inputs
         c.bench_function(
-            &format!("djikstra_bheap_matrix_graph(e_{},v_{})", e, v),
+            &format!("floyd_warshall_matrix_graph(e_{},v_{})", e, v),
             |b| {
                 b.iter_batched(
-                    || matrix_graph.clone(),
-                    |graph| djikstra_bheap_matrix(graph, 0),
+                    || matrix_graph.0.clone(),
+                    |graph| floyd_warshall(&graph),
                     criterion::BatchSize::SmallInput
                 );
             },
         );
-
         c.bench_function(
-            &format!("djikstra_array_pq_list_graph(e_{},v_{})", e, v),
+            &format!("johnson_list_graph(e_{},v_{})", e, v),
             |b| {
                 b.iter_batched(
-                    || list_graph.clone(),
-                    |graph| djikstra_array_pq_list_graph(graph, 0),
+                    || list_graph.0.clone(),
+                    |graph| johnson(&graph),
                     criterion::BatchSize::SmallInput
                 );
             },
         );
 
+        // prims needs undirected edges
+        let undirected_graph = ListGraph::from(matrix_graph.undirected());
+
         c.bench_function(
-            &format!("djikstra_array_pq_matrix_graph(e_{},v_{})", e, v),
+            &format!("prims_lazy_list_graph(e_{},v_{})", e, v),
             |b| {
                 b.iter_batched(
-                    || matrix_graph.clone(),
-                    |graph| djikstra_array_pq_matrix(graph, 0),
+                    || undirected_graph.0.clone(),
+                    |graph| prims(graph, 0),
                     criterion::BatchSize::SmallInput
                 );
             },
         );
+
+        bench_prims_eager::<IndexedMinHeap<_>>(
+            c,
+            &format!("prims_eager_list_graph(e_{},v_{})", e, v),
+            &undirected_graph,
+        );
+        bench_prims_eager::<FibonacciHeap<_>>(
+            c,
+            &format!("prims_fib_heap_list_graph(e_{},v_{})", e, v),
+            &undirected_graph,
+        );
+        bench_prims_eager::<PairingHeap<_>>(
+            c,
+            &format!("prims_pairing_heap_list_graph(e_{},v_{})", e, v),
+            &undirected_graph,
+        );
     }
-    
+
+    // point to point queries across a square grid, corner to corner
+    for side in (10..=100).step_by(10) {
+        let v = side * side;
+        let graph = gen_grid_graph(42069, side, side);
+        let target = v - 1;
+
+        bench_djikstra::<BinaryHeap<_>, _>(
+            c,
+            &format!("grid_djikstra_full(v_{})", v),
+            &graph.0,
+        );
+        c.bench_function(&format!("grid_djikstra_early_exit(v_{})", v), |b| {
+            b.iter(|| djikstra_early_exit(&graph, 0, target))
+        });
+        c.bench_function(&format!("grid_bidirectional_djikstra(v_{})", v), |b| {
+            b.iter(|| bidirectional_djikstra(&graph, 0, target))
+        });
+        c.bench_function(&format!("grid_a_star(v_{})", v), |b| {
+            b.iter(|| a_star(&graph, 0, target, manhattan(side, target)))
+        });
+    }
+
+    // mst on large sparse graphs, 4 undirected edges per vertex
+    for v in [1_000, 5_000, 10_000, 50_000, 100_000] {
+        let e = 4 * v;
+        let graph = gen_connected_graph(42069, v, e);
+        let threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+
+        c.bench_function(&format!("sparse_kruskal(e_{},v_{})", e, v), |b| {
+            b.iter_batched(|| graph.clone(), kruskal, criterion::BatchSize::LargeInput)
+        });
+        c.bench_function(&format!("sparse_boruvka(e_{},v_{})", e, v), |b| {
+            b.iter_batched(|| graph.clone(), boruvka, criterion::BatchSize::LargeInput)
+        });
+        c.bench_function(&format!("sparse_boruvka_parallel(e_{},v_{})", e, v), |b| {
+            b.iter_batched(
+                || graph.clone(),
+                |graph| boruvka_parallel(graph, threads),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+
     for v in (0..=1000).step_by(20) {
         // need minus the diagonal
         let e = usize::pow(v, 2) - v;
-        let matrix_graph = gen_graph(42069,v, e);
+        let matrix_graph = gen_graph(42069, v, e);
         let list_graph = ListGraph::from(matrix_graph.clone());
         assert_graph_edge(&matrix_graph, e);
 
-        c.bench_function(
+        bench_djikstra::<BinaryHeap<_>, _>(
+            c,
             &format!("complete_graph_djikstra_bheap_list_graph(e_{},v_{})", e, v),
-            |b| {
-                b.iter_batched(
-                    || list_graph.clone(),
-                    |graph| djikstra_bheap_list_graph(graph, 0),
-                    criterion::BatchSize::SmallInput
-                );
-            },
+            &list_graph.0,
         );
-
-        c.bench_function(
+        bench_djikstra::<BinaryHeap<_>, _>(
+            c,
             &format!("complete_graph_djikstra_bheap_matrix_graph(e_{},v_{})", e, v),
-            |b| {
-                b.iter_batched(
-                    || matrix_graph.clone(),
-                    |graph| djikstra_bheap_matrix(graph, 0),
-                    criterion::BatchSize::SmallInput
-                );
-            },
+            &matrix_graph.0,
         );
-
-        c.bench_function(
+        bench_djikstra::<ArrayPriorityQueue<_>, _>(
+            c,
             &format!("complete_graph_djikstra_array_pq_list_graph(e_{},v_{})", e, v),
-            |b| {
-                b.iter_batched(
-                    || list_graph.clone(),
-                    |graph| djikstra_array_pq_list_graph(graph, 0),
-                    criterion::BatchSize::SmallInput
-                );
-            },
+            &list_graph.0,
         );
-
-        c.bench_function(
+        bench_djikstra::<ArrayPriorityQueue<_>, _>(
+            c,
             &format!("complete_graph_djikstra_array_pq_matrix_graph(e_{},v_{})", e, v),
-            |b| {
-                b.iter_batched(
-                    || matrix_graph.clone(),
-                    |graph| djikstra_array_pq_matrix(graph, 0),
-                    criterion::BatchSize::SmallInput
-                );
-            },
+            &matrix_graph.0,
+        );
+        bench_djikstra::<RadixHeap, _>(
+            c,
+            &format!("complete_graph_djikstra_radix_heap_list_graph(e_{},v_{})", e, v),
+            &list_graph.0,
+        );
+        bench_djikstra::<RadixHeap, _>(
+            c,
+            &format!("complete_graph_djikstra_radix_heap_matrix_graph(e_{},v_{})", e, v),
+            &matrix_graph.0,
         );
     }
 }
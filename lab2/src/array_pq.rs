@@ -1,13 +1,21 @@
 use std::collections::VecDeque;
 
 use num_traits::{Bounded, Num};
-use sc2001::graph::Edge;
+use sc2001::{graph::Edge, priority_queue::PriorityQueue};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ArrayPriorityQueue<T> {
     data: VecDeque<T>,
 }
 
+impl<T> Default for ArrayPriorityQueue<T> {
+    fn default() -> Self {
+        Self {
+            data: VecDeque::new(),
+        }
+    }
+}
+
 impl<T> From<VecDeque<T>> for ArrayPriorityQueue<T> {
     fn from(data: VecDeque<T>) -> Self {
         Self { data }
@@ -42,7 +50,10 @@ impl<T> ArrayPriorityQueue<Edge<T>>
 where
     T: Num + Bounded + PartialOrd + Copy,
 {
-    pub fn pop(&mut self) -> Option<Edge<T>> {
+    /// Removes an edge with the smallest weight, the last one on ties.
+    /// `PriorityQueue::pop` also gives a cheapest edge but breaks ties by
+    /// vertex.
+    pub fn pop_min(&mut self) -> Option<Edge<T>> {
         if self.data.is_empty() {
             return None;
         }
@@ -68,7 +79,9 @@ where
 }
 
 impl ArrayPriorityQueue<u32> {
-    pub fn pop(&mut self) -> Option<u32> {
+    /// Removes the smallest value, unlike `PriorityQueue::pop` which gives
+    /// the greatest
+    pub fn pop_min(&mut self) -> Option<u32> {
         if self.data.is_empty() {
             return None;
         }
@@ -93,6 +106,23 @@ impl ArrayPriorityQueue<u32> {
     }
 }
 
+/// Linear scan for the greatest item, same convention as `BinaryHeap` so
+/// that `Edge`s come out cheapest first.
+impl<T: Ord> PriorityQueue<T> for ArrayPriorityQueue<T> {
+    fn push(&mut self, item: T) {
+        self.data.push_back(item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let (i, _) = self.data.iter().enumerate().max_by(|(_, a), (_, b)| a.cmp(b))?;
+        self.data.swap_remove_back(i)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sc2001::graph::Edge;
@@ -102,7 +132,7 @@ mod tests {
     #[test]
     fn get_min() {
         let mut array_pq = ArrayPriorityQueue::from(vec![21389, 12389, 1, 1239, 2139]);
-        let smallest = array_pq.pop();
+        let smallest = array_pq.pop_min();
         assert_eq!(smallest, Some(1));
     }
 
@@ -110,38 +140,54 @@ mod tests {
     fn get_min_pair() {
         let mut array_pq =
             ArrayPriorityQueue::from(vec![Edge(21389, 0), Edge(12389, 1), Edge(1, 2), Edge(1239, 3), Edge(2139, 4)]);
-        let smallest = array_pq.pop();
+        let smallest = array_pq.pop_min();
         assert_eq!(smallest, Some(Edge(1, 2)));
     }
 
     #[test]
     fn get_min_same() {
         let mut array_pq = ArrayPriorityQueue::from(vec![1, 1, 1, 1, 1]);
-        let smallest = array_pq.pop();
+        let smallest = array_pq.pop_min();
         assert_eq!(smallest, Some(1));
     }
 
     #[test]
     fn get_min_same_pair() {
         let mut array_pq = ArrayPriorityQueue::from(vec![Edge(1, 0), Edge(1, 1), Edge(1, 2), Edge(1, 3), Edge(1, 4)]);
-        let smallest = array_pq.pop();
+        let smallest = array_pq.pop_min();
         assert_eq!(smallest, Some(Edge(1, 4)));
     }
 
     #[test]
     fn get_min_empty() {
         let mut array_pq = ArrayPriorityQueue::<u32>::from(vec![]);
-        let smallest = array_pq.pop();
+        let smallest = array_pq.pop_min();
         assert_eq!(smallest, None);
     }
 
     #[test]
     fn get_min_one() {
         let mut array_pq = ArrayPriorityQueue::from(vec![1]);
-        let smallest = array_pq.pop();
+        let smallest = array_pq.pop_min();
         assert_eq!(smallest, Some(1));
     }
 
+    #[test]
+    fn trait_pop_cheapest_edge() {
+        use sc2001::priority_queue::PriorityQueue;
+
+        let mut array_pq = ArrayPriorityQueue::default();
+        for e in [Edge(5u32, 0), Edge(1, 1), Edge(3, 2), Edge(1, 3)] {
+            PriorityQueue::push(&mut array_pq, e);
+        }
+
+        let mut popped = vec![];
+        while let Some(e) = PriorityQueue::pop(&mut array_pq) {
+            popped.push(e);
+        }
+        assert_eq!(popped, vec![Edge(1, 3), Edge(1, 1), Edge(3, 2), Edge(5, 0)]);
+    }
+
     #[test]
     fn test_push() {
         let mut array_pq = ArrayPriorityQueue::from(vec![1]);
@@ -0,0 +1,305 @@
+use sc2001::priority_queue::IndexedPriorityQueue;
+
+/// Node of the heap, the node for vertex `v` always lives at index `v`
+#[derive(Debug, Clone)]
+struct Node<K> {
+    /// `None` when the vertex is not queued
+    key: Option<K>,
+    parent: Option<usize>,
+    child: Option<usize>,
+    /// siblings form a circular doubly linked list
+    left: usize,
+    right: usize,
+    degree: usize,
+    marked: bool,
+}
+
+/// Fibonacci heap over the vertex ids `0..n`.
+///
+/// Nodes are stored in an arena indexed by vertex id instead of behind
+/// pointers, the sibling and child links are indices into it.
+///
+/// # Time Complexity (amortised)
+/// - push, decrease_key: O(1)
+/// - pop_min: O(lgn)
+#[derive(Debug, Clone)]
+pub struct FibonacciHeap<K> {
+    nodes: Vec<Node<K>>,
+    min: Option<usize>,
+    len: usize,
+}
+
+impl<K: Copy + Ord> FibonacciHeap<K> {
+    pub fn new(n: usize) -> Self {
+        let nodes = (0..n)
+            .map(|i| Node {
+                key: None,
+                parent: None,
+                child: None,
+                left: i,
+                right: i,
+                degree: 0,
+                marked: false,
+            })
+            .collect();
+
+        Self {
+            nodes,
+            min: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, vertex: usize) -> bool {
+        self.nodes[vertex].key.is_some()
+    }
+
+    /// Queues `vertex` with `key` as a new tree in the root list.
+    ///
+    /// # Panics
+    /// If `vertex` is already in the heap
+    pub fn push(&mut self, vertex: usize, key: K) {
+        assert!(!self.contains(vertex), "vertex {vertex} is already queued");
+
+        let node = &mut self.nodes[vertex];
+        node.key = Some(key);
+        node.parent = None;
+        node.child = None;
+        node.degree = 0;
+        node.marked = false;
+        node.left = vertex;
+        node.right = vertex;
+
+        self.add_root(vertex);
+        self.len += 1;
+    }
+
+    /// Lowers the key of `vertex`, cutting it from its parent if the heap
+    /// order is violated.
+    ///
+    /// # Panics
+    /// If `vertex` is not queued or `key` is larger than its current key
+    pub fn decrease_key(&mut self, vertex: usize, key: K) {
+        let curr = self.nodes[vertex].key.expect("vertex is not queued");
+        assert!(key <= curr, "new key is larger than current key");
+        self.nodes[vertex].key = Some(key);
+
+        if let Some(parent) = self.nodes[vertex].parent {
+            if key < self.key(parent) {
+                self.cut(vertex, parent);
+                self.cascading_cut(parent);
+            }
+        }
+
+        if key < self.key(self.min.unwrap()) {
+            self.min = Some(vertex);
+        }
+    }
+
+    /// Removes the vertex with the smallest key, returns `(vertex, key)`
+    pub fn pop_min(&mut self) -> Option<(usize, K)> {
+        let z = self.min?;
+
+        // every child of z becomes a root
+        while let Some(child) = self.nodes[z].child {
+            self.remove_child(child, z);
+            self.add_root(child);
+        }
+
+        let next = self.nodes[z].right;
+        self.unlink(z);
+        self.len -= 1;
+
+        if next == z {
+            self.min = None;
+        } else {
+            self.min = Some(next);
+            self.consolidate();
+        }
+
+        let key = self.nodes[z].key.take()?;
+        Some((z, key))
+    }
+
+    fn key(&self, vertex: usize) -> K {
+        self.nodes[vertex].key.unwrap()
+    }
+
+    /// Removes `x` from its sibling list, leaving it as a singleton list
+    fn unlink(&mut self, x: usize) {
+        let (l, r) = (self.nodes[x].left, self.nodes[x].right);
+        self.nodes[l].right = r;
+        self.nodes[r].left = l;
+        self.nodes[x].left = x;
+        self.nodes[x].right = x;
+    }
+
+    /// Inserts the singleton `x` to the right of `y` in y's sibling list
+    fn splice(&mut self, x: usize, y: usize) {
+        let r = self.nodes[y].right;
+        self.nodes[x].left = y;
+        self.nodes[x].right = r;
+        self.nodes[r].left = x;
+        self.nodes[y].right = x;
+    }
+
+    /// Adds the singleton `x` to the root list, updating the minimum
+    fn add_root(&mut self, x: usize) {
+        self.nodes[x].parent = None;
+        match self.min {
+            None => self.min = Some(x),
+            Some(min) => {
+                self.splice(x, min);
+                if self.key(x) < self.key(min) {
+                    self.min = Some(x);
+                }
+            }
+        }
+    }
+
+    /// Detaches `x` from the child list of `parent`
+    fn remove_child(&mut self, x: usize, parent: usize) {
+        if self.nodes[parent].child == Some(x) {
+            let sibling = self.nodes[x].right;
+            self.nodes[parent].child = if sibling == x { None } else { Some(sibling) };
+        }
+
+        self.unlink(x);
+        self.nodes[parent].degree -= 1;
+        self.nodes[x].parent = None;
+    }
+
+    /// Makes the root `y` a child of the root `x`
+    fn link(&mut self, y: usize, x: usize) {
+        self.unlink(y);
+        match self.nodes[x].child {
+            None => self.nodes[x].child = Some(y),
+            Some(child) => self.splice(y, child),
+        }
+        self.nodes[y].parent = Some(x);
+        self.nodes[y].marked = false;
+        self.nodes[x].degree += 1;
+    }
+
+    /// Links roots of equal degree until every root has a distinct degree
+    fn consolidate(&mut self) {
+        let start = self.min.unwrap();
+        let mut roots = vec![start];
+        let mut curr = self.nodes[start].right;
+        while curr != start {
+            roots.push(curr);
+            curr = self.nodes[curr].right;
+        }
+
+        let mut by_degree: Vec<Option<usize>> = vec![];
+        for mut x in roots {
+            let mut d = self.nodes[x].degree;
+            while let Some(Some(mut y)) = by_degree.get(d).copied() {
+                if self.key(y) < self.key(x) {
+                    std::mem::swap(&mut x, &mut y);
+                }
+                self.link(y, x);
+                by_degree[d] = None;
+                d += 1;
+            }
+
+            if by_degree.len() <= d {
+                by_degree.resize(d + 1, None);
+            }
+            by_degree[d] = Some(x);
+        }
+
+        // rebuild the root list from the remaining trees
+        self.min = None;
+        for x in by_degree.into_iter().flatten() {
+            self.unlink(x);
+            self.add_root(x);
+        }
+    }
+
+    fn cut(&mut self, x: usize, parent: usize) {
+        self.remove_child(x, parent);
+        self.nodes[x].marked = false;
+        self.add_root(x);
+    }
+
+    fn cascading_cut(&mut self, mut y: usize) {
+        while let Some(parent) = self.nodes[y].parent {
+            if !self.nodes[y].marked {
+                self.nodes[y].marked = true;
+                return;
+            }
+
+            self.cut(y, parent);
+            y = parent;
+        }
+    }
+}
+
+impl<K: Copy + Ord> IndexedPriorityQueue<K> for FibonacciHeap<K> {
+    fn with_vertices(n: usize) -> Self {
+        Self::new(n)
+    }
+
+    fn push(&mut self, vertex: usize, key: K) {
+        FibonacciHeap::push(self, vertex, key)
+    }
+
+    fn decrease_key(&mut self, vertex: usize, key: K) {
+        FibonacciHeap::decrease_key(self, vertex, key)
+    }
+
+    fn contains(&self, vertex: usize) -> bool {
+        FibonacciHeap::contains(self, vertex)
+    }
+
+    fn pop_min(&mut self) -> Option<(usize, K)> {
+        FibonacciHeap::pop_min(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        FibonacciHeap::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sc2001::test_utils::check_indexed_pq;
+
+    use super::FibonacciHeap;
+
+    #[test]
+    fn pop_in_key_order() {
+        let mut heap = FibonacciHeap::new(5);
+        for (v, k) in [(0, 9), (1, 4), (2, 7), (3, 1), (4, 5)] {
+            heap.push(v, k);
+        }
+
+        // consolidates the trees so the decrease below has to cut
+        assert_eq!(heap.pop_min(), Some((3, 1)));
+        heap.decrease_key(0, 0);
+
+        let mut popped = vec![];
+        while let Some(x) = heap.pop_min() {
+            popped.push(x);
+        }
+
+        assert_eq!(popped, vec![(0, 0), (1, 4), (4, 5), (2, 7)]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn random_ops() {
+        for seed in 0..20 {
+            check_indexed_pq(FibonacciHeap::new(500), 500, seed);
+        }
+    }
+}
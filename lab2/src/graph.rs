@@ -19,6 +19,42 @@ impl From<Vec<Vec<Edge<u32>>>> for ListGraph {
     }
 }
 
+impl MatrixGraph {
+    /// Symmetric copy of the graph for algorithms that need undirected
+    /// edges such as Prim's, keeping the cheaper weight of `i -> j` and
+    /// `j -> i` when both exist
+    pub fn undirected(&self) -> Self {
+        let old = &self.internal_repr.0;
+        let mut mat = old.clone();
+
+        for (i, row) in mat.iter_mut().enumerate() {
+            for (j, w) in row.iter_mut().enumerate() {
+                *w = match (old[i][j], old[j][i]) {
+                    (0, w) | (w, 0) => w,
+                    (a, b) => u32::min(a, b),
+                };
+            }
+        }
+
+        Self::from(mat)
+    }
+}
+
+impl ListGraph {
+    /// Copy of the graph with every edge pointing the other way
+    pub fn reversed(&self) -> Self {
+        let mut list = vec![vec![]; self.len()];
+
+        for (i, edges) in self.internal_repr.0.iter().enumerate() {
+            for Edge(weight, j) in edges {
+                list[*j].push(Edge(*weight, i));
+            }
+        }
+
+        Self::from(list)
+    }
+}
+
 impl Deref for MatrixGraph {
     type Target = Graph<AdjMatrix<u32>>;
 
@@ -40,9 +76,9 @@ impl From<MatrixGraph> for ListGraph {
         let sz = graph.len();
         let mut list = vec![vec![]; sz];
 
-        for i in 0..sz {
+        for (i, edges) in list.iter_mut().enumerate() {
             for Edge(weight, j) in graph.neighbours(i).into_iter() {
-                list[i].push(Edge(weight, j));
+                edges.push(Edge(weight, j));
             }
         }
 
@@ -55,9 +91,9 @@ impl From<ListGraph> for MatrixGraph {
         let sz = graph.len();
         let mut mat = vec![vec![0; sz]; sz];
 
-        for i in 0..sz {
+        for (i, row) in mat.iter_mut().enumerate() {
             for Edge(weight, vertex) in graph.0.neighbours(i).iter() {
-                mat[i][*vertex] = *weight;
+                row[*vertex] = *weight;
             }
         }
 
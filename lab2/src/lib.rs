@@ -5,189 +5,40 @@ use std::collections::BinaryHeap;
 use array_pq::ArrayPriorityQueue;
 use graph::{ListGraph, MatrixGraph};
 use nanorand::{Rng, WyRand};
-use sc2001::graph::Edge;
+use radix_heap::RadixHeap;
+use sc2001::djikstra::djikstra_with;
 
 pub mod array_pq;
+pub mod fib_heap;
 pub mod graph;
+pub mod pairing_heap;
+pub mod point_to_point;
+pub mod radix_heap;
 
-#[derive(Debug, Clone)]
-pub struct DjikstraRunInfo {
-    pub distance: Vec<u32>,
-    pub predecessors: Vec<Option<usize>>,
-    pub visited: Vec<bool>,
-}
-
-impl DjikstraRunInfo {
-    pub fn new(distance: Vec<u32>, predecessors: Vec<Option<usize>>, visited: Vec<bool>) -> Self {
-        Self {
-            distance,
-            predecessors,
-            visited,
-        }
-    }
-}
-
-fn djikstra_setup<T>(sz_v: usize, src: usize) -> (Vec<u32>, Vec<Option<usize>>, Vec<bool>, T)
-where
-    T: From<[Edge<u32>; 1]>,
-{
-    // initialise distance to every other node as u32::MAX
-    // which is functionally the same as infinity
-    let mut distance = vec![u32::MAX; sz_v];
-    let predecessors = vec![None::<usize>; sz_v];
-    let visited = vec![false; sz_v];
+pub type DjikstraRunInfo = sc2001::djikstra::DjikstraRunInfo<u32>;
 
-    // construct initial priority queue from src's neighbours
-    // where u32 = weight, usize = index
-    let pq = T::from([Edge(0, src)]);
-
-    // src distance to itself is 0
-    distance[src] = 0;
-
-    (distance, predecessors, visited, pq)
-}
-
-#[rustfmt::skip]
 pub fn djikstra_bheap_list_graph(graph: ListGraph, src: usize) -> DjikstraRunInfo {
-    if graph.len() == 0 {
-        return DjikstraRunInfo::new(vec![], vec![], vec![]);
-    }
-
-    let (
-        mut distance,
-        mut predecessors,
-        mut visited,
-        mut pq
-    ) = djikstra_setup::<BinaryHeap<_>>(graph.len(), src);
-
-    while !pq.is_empty() {
-        // get cheapest vertex
-        // SAFETY: unwrap is ok because queue not empty
-        let Edge(_, vertex) = pq.pop().unwrap();
-
-        // visit curr vertex
-        visited[vertex] = true;
-
-        // get vertex neighbours and update distance table from vertex
-        // only visit vertex that have not been visited
-        for Edge(weight, neighbour) in graph.neighbours(vertex) {
-            // update distance table and predecessors
-            if !visited[*neighbour] && distance[*neighbour] > distance[vertex] + weight {
-                distance[*neighbour] = distance[vertex] + weight;
-                predecessors[*neighbour] = Some(vertex);
-                pq.push(Edge(distance[*neighbour], *neighbour));
-            }
-        }
-    }
-    DjikstraRunInfo::new(distance, predecessors, visited)
+    djikstra_with::<BinaryHeap<_>, _, _>(&graph.0, src)
 }
 
-#[rustfmt::skip]
 pub fn djikstra_bheap_matrix(graph: MatrixGraph, src: usize) -> DjikstraRunInfo {
-    if graph.len() == 0 {
-        return DjikstraRunInfo::new(vec![], vec![], vec![]);
-    }
-
-    let (
-        mut distance,
-        mut predecessors,
-        mut visited,
-        mut pq
-    ) = djikstra_setup::<BinaryHeap<_>>(graph.len(), src);
-
-    while !pq.is_empty() {
-        // get cheapest vertex
-        // SAFETY: unwrap is ok because queue not empty
-        let Edge(_, vertex) = pq.pop().unwrap();
-
-        // visit curr vertex
-        visited[vertex] = true;
-
-        // get vertex neighbours and update distance table from vertex
-        // only visit vertex that have not been visited
-        for Edge(weight, neighbour) in &graph.neighbours(vertex) {
-            // update distance table and predecessors
-            if !visited[*neighbour] && distance[*neighbour] > distance[vertex] + weight {
-                distance[*neighbour] = distance[vertex] + weight;
-                predecessors[*neighbour] = Some(vertex);
-                pq.push(Edge(distance[*neighbour], *neighbour));
-            }
-        }
-    }
-    
-    DjikstraRunInfo::new(distance, predecessors, visited)
+    djikstra_with::<BinaryHeap<_>, _, _>(&graph.0, src)
 }
 
-#[rustfmt::skip]
 pub fn djikstra_array_pq_list_graph(graph: ListGraph, src: usize) -> DjikstraRunInfo {
-    if graph.len() == 0 {
-        return DjikstraRunInfo::new(vec![], vec![], vec![]);
-    }
-
-    let (
-        mut distance,
-        mut predecessors,
-        mut visited,
-        mut pq
-    ) = djikstra_setup::<ArrayPriorityQueue<_>>(graph.len(), src);
-
-    while !pq.is_empty() {
-        // get cheapest vertex
-        // SAFETY: unwrap is ok because queue not empty
-        let Edge(_, vertex) = pq.pop().unwrap();
-
-        // visit curr vertex
-        visited[vertex] = true;
-
-        // get vertex neighbours and update distance table from vertex
-        // only visit vertex that have not been visited
-        for Edge(weight, neighbour) in graph.neighbours(vertex) {
-            // update distance table and predecessors
-            if !visited[*neighbour] && distance[*neighbour] > distance[vertex] + weight {
-                distance[*neighbour] = distance[vertex] + weight;
-                predecessors[*neighbour] = Some(vertex);
-                pq.push(Edge(distance[*neighbour], *neighbour));
-            }
-        }
-    }
-    
-    DjikstraRunInfo::new(distance, predecessors, visited)
+    djikstra_with::<ArrayPriorityQueue<_>, _, _>(&graph.0, src)
 }
 
-#[rustfmt::skip]
 pub fn djikstra_array_pq_matrix(graph: MatrixGraph, src: usize) -> DjikstraRunInfo {
-    if graph.len() == 0 {
-        return DjikstraRunInfo::new(vec![], vec![], vec![]);
-    }
+    djikstra_with::<ArrayPriorityQueue<_>, _, _>(&graph.0, src)
+}
 
-    let (
-        mut distance,
-        mut predecessors,
-        mut visited,
-        mut pq
-    ) = djikstra_setup::<ArrayPriorityQueue<_>>(graph.len(), src);
-
-    while !pq.is_empty() {
-        // get cheapest vertex
-        // SAFETY: unwrap is ok because queue not empty
-        let Edge(_, vertex) = pq.pop().unwrap();
-
-        // visit curr vertex
-        visited[vertex] = true;
-
-        // get vertex neighbours and update distance table from vertex
-        // only visit vertex that have not been visited
-        for Edge(weight, neighbour) in &graph.neighbours(vertex) {
-            // update distance table and predecessors
-            if !visited[*neighbour] && distance[*neighbour] > distance[vertex] + weight {
-                distance[*neighbour] = distance[vertex] + weight;
-                predecessors[*neighbour] = Some(vertex);
-                pq.push(Edge(distance[*neighbour], *neighbour));
-            }
-        }
-    }
-    
-    DjikstraRunInfo::new(distance, predecessors, visited)
+pub fn djikstra_radix_heap_list_graph(graph: ListGraph, src: usize) -> DjikstraRunInfo {
+    djikstra_with::<RadixHeap, _, _>(&graph.0, src)
+}
+
+pub fn djikstra_radix_heap_matrix(graph: MatrixGraph, src: usize) -> DjikstraRunInfo {
+    djikstra_with::<RadixHeap, _, _>(&graph.0, src)
 }
 
 pub fn gen_graph<T>(seed: T, v: usize, e: usize) -> MatrixGraph
@@ -241,14 +92,23 @@ pub fn assert_graph_edge(x: &MatrixGraph, e: usize) {
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, io::{Read, Write}};
-
-    use sc2001::Estimates;
+    use std::{collections::BinaryHeap, fs::File, io::{Read, Write}};
+
+    use sc2001::{
+        arborescence::chu_liu_edmonds,
+        djikstra::djikstra_eager_with,
+        predecessor_tree::PredecessorTree,
+        prims::{prims, prims_eager_with, prims_with},
+        Estimates,
+    };
 
     use crate::{
-        assert_graph_edge, djikstra_array_pq_list_graph, djikstra_array_pq_matrix,
-        djikstra_bheap_list_graph, djikstra_bheap_matrix, gen_graph,
+        array_pq::ArrayPriorityQueue, assert_graph_edge, djikstra_array_pq_list_graph,
+        djikstra_array_pq_matrix, djikstra_bheap_list_graph, djikstra_bheap_matrix,
+        djikstra_radix_heap_list_graph, djikstra_radix_heap_matrix, fib_heap::FibonacciHeap,
+        gen_graph,
         graph::{ListGraph, MatrixGraph},
+        pairing_heap::PairingHeap,
     };
 
     fn mat1() -> (Vec<Vec<u32>>, Vec<u32>) {
@@ -332,6 +192,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_djikstra_radix_heap() {
+        for (mat, res) in all_graphs() {
+            let mat_graph = MatrixGraph::from(mat);
+            let list_graph = ListGraph::from(mat_graph.clone());
+            assert_eq!(res, djikstra_radix_heap_matrix(mat_graph, 0).distance);
+            assert_eq!(res, djikstra_radix_heap_list_graph(list_graph, 0).distance);
+        }
+
+        let mat_graph = gen_graph(42069, 100, 2000);
+        let expected = djikstra_bheap_matrix(mat_graph.clone(), 0);
+        assert_eq!(expected.distance, djikstra_radix_heap_matrix(mat_graph, 0).distance);
+    }
+
+    #[test]
+    fn test_djikstra_decrease_key_heaps() {
+        for (mat, res) in all_graphs() {
+            let list_graph = ListGraph::from(MatrixGraph::from(mat));
+            let fib = djikstra_eager_with::<FibonacciHeap<_>, _, _>(&list_graph.0, 0);
+            let pairing = djikstra_eager_with::<PairingHeap<_>, _, _>(&list_graph.0, 0);
+            assert_eq!(res, fib.distance);
+            assert_eq!(res, pairing.distance);
+        }
+
+        let list_graph = ListGraph::from(gen_graph(42069, 100, 2000));
+        let expected = djikstra_bheap_list_graph(list_graph.clone(), 0);
+        let fib = djikstra_eager_with::<FibonacciHeap<_>, _, _>(&list_graph.0, 0);
+        let pairing = djikstra_eager_with::<PairingHeap<_>, _, _>(&list_graph.0, 0);
+        assert_eq!(expected.distance, fib.distance);
+        assert_eq!(expected.distance, pairing.distance);
+    }
+
+    #[test]
+    fn prims_any_queue() {
+        let mat_graph = MatrixGraph::from(vec![
+            vec![0, 1, 7, 0, 0],
+            vec![1, 0, 5, 4, 3],
+            vec![7, 5, 0, 0, 6],
+            vec![0, 4, 0, 0, 2],
+            vec![0, 3, 6, 2, 0],
+        ]);
+        let list_graph = ListGraph::from(mat_graph);
+
+        let bheap = prims_with::<BinaryHeap<_>>(list_graph.0.clone(), 0).unwrap();
+        let array_pq = prims_with::<ArrayPriorityQueue<_>>(list_graph.0.clone(), 0).unwrap();
+        let fib = prims_eager_with::<FibonacciHeap<_>>(list_graph.0.clone(), 0).unwrap();
+        let pairing = prims_eager_with::<PairingHeap<_>>(list_graph.0, 0).unwrap();
+        assert_eq!(bheap.min_cost, 11);
+        assert_eq!(array_pq.min_cost, 11);
+        assert_eq!(fib.min_cost, 11);
+        assert_eq!(pairing.min_cost, 11);
+    }
+
+    #[test]
+    fn arborescence_directed_graph() {
+        let mat_graph = gen_graph(42069, 100, 2000);
+        let list_graph = ListGraph::from(mat_graph.clone());
+        let arb = chu_liu_edmonds(&list_graph.0, 0).unwrap();
+
+        assert!(arb.depths().iter().all(|d| d.is_some()));
+        for (from, edge) in arb.tree_edges() {
+            assert!(list_graph.neighbours(from).contains(&edge));
+        }
+
+        // ignoring direction can only make the tree cheaper
+        let undirected = ListGraph::from(mat_graph.undirected());
+        assert!(prims(undirected.0, 0).unwrap().min_cost <= arb.cost);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        let mat_graph = MatrixGraph::from(mat1().0).undirected();
+        let mat = &mat_graph.internal_repr.0;
+        for (i, row) in mat.iter().enumerate() {
+            for (j, w) in row.iter().enumerate() {
+                assert_eq!(*w, mat[j][i]);
+            }
+        }
+        assert_eq!(mat[1][2], 2);
+        assert_eq!(mat[0][1], 10);
+    }
+
     #[test]
     fn generate_graph() {
         let x = gen_graph(42069, 20, 20);
@@ -346,7 +288,7 @@ mod tests {
     #[test]
     fn compile_density_test() {
         let mut all_estimates = String::from(
-                "e,v,bheap_list_graph_mean,bheap_matrix_graph_mean,array_pq_list_graph_mean,array_pq_matrix_graph\n");
+                "e,v,bheap_list_graph_mean,bheap_matrix_graph_mean,array_pq_list_graph_mean,array_pq_matrix_graph,radix_heap_list_graph_mean,radix_heap_matrix_graph_mean\n");
         for e in (100..=9900).step_by(100) {
             let v = 100;
 
@@ -355,6 +297,8 @@ mod tests {
                 format!("djikstra_bheap_matrix_graph(e_{},v_{})", e, v),
                 format!("djikstra_array_pq_list_graph(e_{},v_{})", e, v),
                 format!("djikstra_array_pq_matrix_graph(e_{},v_{})", e, v),
+                format!("djikstra_radix_heap_list_graph(e_{},v_{})", e, v),
+                format!("djikstra_radix_heap_matrix_graph(e_{},v_{})", e, v),
             ];
 
             all_estimates.push_str(&format!("{e},{v},"));
@@ -369,18 +313,90 @@ mod tests {
                 let mean = estimates.mean.point_estimate / 1000.0;
                 all_estimates.push_str(&format!("{mean},"));
             }
-            all_estimates.push_str("\n");
+            all_estimates.push('\n');
         }
         
         let mut density_test = File::create("density_test.csv").unwrap();
         density_test.write_all(all_estimates.as_bytes()).unwrap();
     }
     
+    #[ignore]
+    #[test]
+    fn compile_eager_lazy_test() {
+        let mut all_estimates = String::from(
+                "e,v,djikstra_lazy_list_graph_mean,djikstra_eager_list_graph_mean,djikstra_lazy_matrix_graph_mean,djikstra_eager_matrix_graph_mean,djikstra_fib_heap_list_graph_mean,djikstra_pairing_heap_list_graph_mean,prims_lazy_list_graph_mean,prims_eager_list_graph_mean,prims_fib_heap_list_graph_mean,prims_pairing_heap_list_graph_mean\n");
+        for e in (100..=9900).step_by(100) {
+            let v = 100;
+
+            let benches = [
+                format!("djikstra_bheap_list_graph(e_{},v_{})", e, v),
+                format!("djikstra_eager_list_graph(e_{},v_{})", e, v),
+                format!("djikstra_bheap_matrix_graph(e_{},v_{})", e, v),
+                format!("djikstra_eager_matrix_graph(e_{},v_{})", e, v),
+                format!("djikstra_fib_heap_list_graph(e_{},v_{})", e, v),
+                format!("djikstra_pairing_heap_list_graph(e_{},v_{})", e, v),
+                format!("prims_lazy_list_graph(e_{},v_{})", e, v),
+                format!("prims_eager_list_graph(e_{},v_{})", e, v),
+                format!("prims_fib_heap_list_graph(e_{},v_{})", e, v),
+                format!("prims_pairing_heap_list_graph(e_{},v_{})", e, v),
+            ];
+
+            all_estimates.push_str(&format!("{e},{v},"));
+
+            for b in benches {
+                let mut buf = String::new();
+                let file_name = format!("density_test/criterion/{b}/new/estimates.json");
+                dbg!(&file_name);
+                let mut f = File::open(file_name).unwrap();
+                let _ = f.read_to_string(&mut buf).unwrap();
+                let estimates = serde_json::from_str::<Estimates>(&buf).unwrap();
+                let mean = estimates.mean.point_estimate / 1000.0;
+                all_estimates.push_str(&format!("{mean},"));
+            }
+            all_estimates.push('\n');
+        }
+
+        let mut eager_lazy_test = File::create("eager_lazy_test.csv").unwrap();
+        eager_lazy_test.write_all(all_estimates.as_bytes()).unwrap();
+    }
+
+    #[ignore]
+    #[test]
+    fn compile_all_pairs_test() {
+        let mut all_estimates = String::from(
+                "e,v,floyd_warshall_matrix_graph_mean,johnson_list_graph_mean\n");
+        for e in (100..=9900).step_by(100) {
+            let v = 100;
+
+            let benches = [
+                format!("floyd_warshall_matrix_graph(e_{},v_{})", e, v),
+                format!("johnson_list_graph(e_{},v_{})", e, v),
+            ];
+
+            all_estimates.push_str(&format!("{e},{v},"));
+
+            for b in benches {
+                let mut buf = String::new();
+                let file_name = format!("density_test/criterion/{b}/new/estimates.json");
+                dbg!(&file_name);
+                let mut f = File::open(file_name).unwrap();
+                let _ = f.read_to_string(&mut buf).unwrap();
+                let estimates = serde_json::from_str::<Estimates>(&buf).unwrap();
+                let mean = estimates.mean.point_estimate / 1000.0;
+                all_estimates.push_str(&format!("{mean},"));
+            }
+            all_estimates.push('\n');
+        }
+
+        let mut all_pairs_test = File::create("all_pairs_test.csv").unwrap();
+        all_pairs_test.write_all(all_estimates.as_bytes()).unwrap();
+    }
+
     #[ignore]
     #[test]
     fn compile_stress_test() {
         let mut all_estimates = String::from(
-                "e,v,bheap_list_graph_mean,bheap_matrix_graph_mean,array_pq_list_graph_mean,array_pq_matrix_graph\n");
+                "e,v,bheap_list_graph_mean,bheap_matrix_graph_mean,array_pq_list_graph_mean,array_pq_matrix_graph,radix_heap_list_graph_mean,radix_heap_matrix_graph_mean\n");
                 
         for v in (0..=1000).step_by(20) {
 
@@ -390,6 +406,8 @@ mod tests {
                 format!("complete_graph_djikstra_bheap_matrix_graph(e_{},v_{})", e, v),
                 format!("complete_graph_djikstra_array_pq_list_graph(e_{},v_{})", e, v),
                 format!("complete_graph_djikstra_array_pq_matrix_graph(e_{},v_{})", e, v),
+                format!("complete_graph_djikstra_radix_heap_list_graph(e_{},v_{})", e, v),
+                format!("complete_graph_djikstra_radix_heap_matrix_graph(e_{},v_{})", e, v),
             ];
 
             all_estimates.push_str(&format!("{e},{v},"));
@@ -405,7 +423,7 @@ mod tests {
                 all_estimates.push_str(&format!("{mean},"));
             }
             
-            all_estimates.push_str("\n");
+            all_estimates.push('\n');
         }
         
         let mut density_test = File::create("stress_test.csv").unwrap();
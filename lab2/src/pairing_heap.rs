@@ -0,0 +1,241 @@
+use sc2001::priority_queue::IndexedPriorityQueue;
+
+/// Node of the heap, the node for vertex `v` always lives at index `v`
+#[derive(Debug, Clone)]
+struct Node<K> {
+    /// `None` when the vertex is not queued
+    key: Option<K>,
+    /// leftmost child
+    child: Option<usize>,
+    /// next sibling to the right
+    next: Option<usize>,
+    /// previous sibling, or the parent for a leftmost child
+    prev: Option<usize>,
+}
+
+/// Pairing heap over the vertex ids `0..n`.
+///
+/// Like [`FibonacciHeap`](crate::fib_heap::FibonacciHeap) the nodes live in
+/// an arena indexed by vertex id. Each node keeps its leftmost child and its
+/// siblings, so a subtree can be cut out in O(1) for `decrease_key`.
+///
+/// # Time Complexity (amortised)
+/// - push: O(1)
+/// - decrease_key: o(lgn)
+/// - pop_min: O(lgn)
+#[derive(Debug, Clone)]
+pub struct PairingHeap<K> {
+    nodes: Vec<Node<K>>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<K: Copy + Ord> PairingHeap<K> {
+    pub fn new(n: usize) -> Self {
+        let nodes = (0..n)
+            .map(|_| Node {
+                key: None,
+                child: None,
+                next: None,
+                prev: None,
+            })
+            .collect();
+
+        Self {
+            nodes,
+            root: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, vertex: usize) -> bool {
+        self.nodes[vertex].key.is_some()
+    }
+
+    /// Queues `vertex` with `key` by melding it with the root.
+    ///
+    /// # Panics
+    /// If `vertex` is already in the heap
+    pub fn push(&mut self, vertex: usize, key: K) {
+        assert!(!self.contains(vertex), "vertex {vertex} is already queued");
+
+        self.nodes[vertex] = Node {
+            key: Some(key),
+            child: None,
+            next: None,
+            prev: None,
+        };
+
+        self.root = Some(self.meld(self.root, vertex));
+        self.len += 1;
+    }
+
+    /// Lowers the key of `vertex`, its subtree is cut out and melded with
+    /// the root.
+    ///
+    /// # Panics
+    /// If `vertex` is not queued or `key` is larger than its current key
+    pub fn decrease_key(&mut self, vertex: usize, key: K) {
+        let curr = self.nodes[vertex].key.expect("vertex is not queued");
+        assert!(key <= curr, "new key is larger than current key");
+        self.nodes[vertex].key = Some(key);
+
+        if self.root == Some(vertex) {
+            return;
+        }
+
+        self.detach(vertex);
+        self.root = Some(self.meld(self.root, vertex));
+    }
+
+    /// Removes the vertex with the smallest key, returns `(vertex, key)`
+    pub fn pop_min(&mut self) -> Option<(usize, K)> {
+        let root = self.root?;
+
+        // collect the children of the root as separate trees
+        let mut trees = vec![];
+        let mut curr = self.nodes[root].child;
+        while let Some(c) = curr {
+            curr = self.nodes[c].next;
+            self.nodes[c].next = None;
+            self.nodes[c].prev = None;
+            trees.push(c);
+        }
+        self.nodes[root].child = None;
+
+        // first pass: meld pairs left to right
+        // second pass: meld the results right to left
+        let paired = trees
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => self.meld(Some(*a), *b),
+                _ => pair[0],
+            })
+            .collect::<Vec<_>>();
+
+        self.root = paired
+            .into_iter()
+            .rev()
+            .fold(None, |acc, tree| Some(self.meld(acc, tree)));
+        self.len -= 1;
+
+        let key = self.nodes[root].key.take()?;
+        Some((root, key))
+    }
+
+    fn key(&self, vertex: usize) -> K {
+        self.nodes[vertex].key.unwrap()
+    }
+
+    /// Melds the tree rooted at `b` into `a`, returns the new root
+    fn meld(&mut self, a: Option<usize>, b: usize) -> usize {
+        let a = match a {
+            Some(a) => a,
+            None => return b,
+        };
+
+        let (parent, child) = if self.key(b) < self.key(a) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        // child becomes the leftmost child of parent
+        let first = self.nodes[parent].child;
+        self.nodes[child].next = first;
+        self.nodes[child].prev = Some(parent);
+        if let Some(first) = first {
+            self.nodes[first].prev = Some(child);
+        }
+        self.nodes[parent].child = Some(child);
+
+        parent
+    }
+
+    /// Cuts the subtree rooted at `x` out of its parent's child list
+    fn detach(&mut self, x: usize) {
+        let (prev, next) = (self.nodes[x].prev, self.nodes[x].next);
+
+        if let Some(prev) = prev {
+            if self.nodes[prev].child == Some(x) {
+                self.nodes[prev].child = next;
+            } else {
+                self.nodes[prev].next = next;
+            }
+        }
+
+        if let Some(next) = next {
+            self.nodes[next].prev = prev;
+        }
+
+        self.nodes[x].prev = None;
+        self.nodes[x].next = None;
+    }
+}
+
+impl<K: Copy + Ord> IndexedPriorityQueue<K> for PairingHeap<K> {
+    fn with_vertices(n: usize) -> Self {
+        Self::new(n)
+    }
+
+    fn push(&mut self, vertex: usize, key: K) {
+        PairingHeap::push(self, vertex, key)
+    }
+
+    fn decrease_key(&mut self, vertex: usize, key: K) {
+        PairingHeap::decrease_key(self, vertex, key)
+    }
+
+    fn contains(&self, vertex: usize) -> bool {
+        PairingHeap::contains(self, vertex)
+    }
+
+    fn pop_min(&mut self) -> Option<(usize, K)> {
+        PairingHeap::pop_min(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        PairingHeap::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sc2001::test_utils::check_indexed_pq;
+
+    use super::PairingHeap;
+
+    #[test]
+    fn pop_in_key_order() {
+        let mut heap = PairingHeap::new(5);
+        for (v, k) in [(0, 9), (1, 4), (2, 7), (3, 1), (4, 5)] {
+            heap.push(v, k);
+        }
+
+        assert_eq!(heap.pop_min(), Some((3, 1)));
+        heap.decrease_key(0, 0);
+
+        let mut popped = vec![];
+        while let Some(x) = heap.pop_min() {
+            popped.push(x);
+        }
+
+        assert_eq!(popped, vec![(0, 0), (1, 4), (4, 5), (2, 7)]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn random_ops() {
+        for seed in 0..20 {
+            check_indexed_pq(PairingHeap::new(500), 500, seed);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::BinaryHeap;
+
+use nanorand::{Rng, WyRand};
+use sc2001::graph::Edge;
+
+use crate::graph::ListGraph;
+
+/// Result of a single `src` to `target` query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathInfo {
+    /// vertices from `src` to `target` inclusive, `None` if unreachable
+    pub path: Option<Vec<usize>>,
+    pub distance: Option<u32>,
+    /// number of vertices taken off the queue, used to compare how much of
+    /// the graph each search has to look at
+    pub settled: usize,
+}
+
+impl PathInfo {
+    pub fn new(path: Option<Vec<usize>>, distance: Option<u32>, settled: usize) -> Self {
+        Self {
+            path,
+            distance,
+            settled,
+        }
+    }
+}
+
+/// Djikstra that stops as soon as `target` is visited instead of building
+/// the whole shortest path tree.
+///
+/// # Time Complexity
+/// - O((V + E)lgV) in the worst case where target is the furthest vertex
+pub fn djikstra_early_exit(graph: &ListGraph, src: usize, target: usize) -> PathInfo {
+    a_star(graph, src, target, |_| 0)
+}
+
+/// A* search guided by `heuristic`, a lower bound on the distance from a
+/// vertex to `target`.
+///
+/// The heuristic must be admissible, i.e. never overestimate, for the path
+/// to be a shortest one. A vertex is expanded again if a shorter path to it
+/// is found later, which only happens if the heuristic is not consistent, so
+/// `settled` may count a vertex more than once.
+pub fn a_star<H>(graph: &ListGraph, src: usize, target: usize, heuristic: H) -> PathInfo
+where
+    H: Fn(usize) -> u32,
+{
+    let sz_v = graph.len();
+    let mut distance = vec![u32::MAX; sz_v];
+    let mut predecessors = vec![None::<usize>; sz_v];
+    let mut pq = BinaryHeap::new();
+    let mut settled = 0;
+
+    distance[src] = 0;
+    pq.push(Edge(heuristic(src), src));
+
+    while let Some(Edge(f, vertex)) = pq.pop() {
+        // stale entry, vertex was pushed again with a shorter distance
+        if f > distance[vertex] + heuristic(vertex) {
+            continue;
+        }
+
+        settled += 1;
+        if vertex == target {
+            let path = walk_back(&predecessors, target);
+            return PathInfo::new(Some(path), Some(distance[target]), settled);
+        }
+
+        for Edge(weight, neighbour) in graph.neighbours(vertex) {
+            let g = distance[vertex] + weight;
+            if g < distance[*neighbour] {
+                distance[*neighbour] = g;
+                predecessors[*neighbour] = Some(vertex);
+                pq.push(Edge(g + heuristic(*neighbour), *neighbour));
+            }
+        }
+    }
+
+    PathInfo::new(None, None, settled)
+}
+
+/// Djikstra from `src` on the graph and from `target` on the reversed graph
+/// at the same time, always advancing the side with the smaller frontier.
+///
+/// The search stops once the two smallest frontier distances add up to at
+/// least the best path seen so far, since no path through an unsettled
+/// vertex can then be shorter. The reversed graph is built on every call.
+///
+/// # Time Complexity
+/// - O((V + E)lgV), but usually settles far fewer vertices than
+///   [`djikstra_early_exit`]
+pub fn bidirectional_djikstra(graph: &ListGraph, src: usize, target: usize) -> PathInfo {
+    let sz_v = graph.len();
+    let reversed = graph.reversed();
+    let graphs = [graph, &reversed];
+
+    // index 0 searches forward from src, index 1 backwards from target
+    let mut distance = [vec![u32::MAX; sz_v], vec![u32::MAX; sz_v]];
+    let mut predecessors = [vec![None::<usize>; sz_v], vec![None::<usize>; sz_v]];
+    let mut visited = [vec![false; sz_v], vec![false; sz_v]];
+    let mut pq: [BinaryHeap<Edge<u32>>; 2] = [BinaryHeap::new(), BinaryHeap::new()];
+    let mut settled = 0;
+
+    distance[0][src] = 0;
+    distance[1][target] = 0;
+    pq[0].push(Edge(0, src));
+    pq[1].push(Edge(0, target));
+
+    // shortest src -> target distance seen so far and where the sides met
+    let mut best = if src == target { 0 } else { u32::MAX };
+    let mut meet = (src == target).then_some(src);
+
+    // either side running dry means every path has been considered
+    while let (Some(&Edge(top_f, _)), Some(&Edge(top_b, _))) = (pq[0].peek(), pq[1].peek()) {
+        if top_f.saturating_add(top_b) >= best {
+            break;
+        }
+
+        let side = if top_f <= top_b { 0 } else { 1 };
+        let Edge(_, vertex) = pq[side].pop().unwrap();
+        if visited[side][vertex] {
+            continue;
+        }
+
+        visited[side][vertex] = true;
+        settled += 1;
+
+        for Edge(weight, neighbour) in graphs[side].neighbours(vertex) {
+            let d = distance[side][vertex] + weight;
+            if d < distance[side][*neighbour] {
+                distance[side][*neighbour] = d;
+                predecessors[side][*neighbour] = Some(vertex);
+                pq[side].push(Edge(d, *neighbour));
+            }
+
+            // the other side has already reached the neighbour
+            let (this, other) = (distance[side][*neighbour], distance[1 - side][*neighbour]);
+            if other != u32::MAX && this.saturating_add(other) < best {
+                best = this + other;
+                meet = Some(*neighbour);
+            }
+        }
+    }
+
+    match meet {
+        Some(meet) => {
+            let mut path = walk_back(&predecessors[0], meet);
+            let mut curr = meet;
+            while let Some(next) = predecessors[1][curr] {
+                path.push(next);
+                curr = next;
+            }
+
+            PathInfo::new(Some(path), Some(best), settled)
+        }
+        None => PathInfo::new(None, None, settled),
+    }
+}
+
+/// Follows `predecessors` back from `target`, returns the path in order
+fn walk_back(predecessors: &[Option<usize>], target: usize) -> Vec<usize> {
+    let mut path = vec![target];
+    let mut curr = target;
+    while let Some(prev) = predecessors[curr] {
+        path.push(prev);
+        curr = prev;
+    }
+
+    path.reverse();
+    path
+}
+
+/// `width` x `height` grid where every cell has an edge to its 4 neighbours.
+/// Vertex `(x, y)` is `y * width + x` and edge weights are in `1..100`, so the
+/// manhattan distance is an admissible heuristic for A*.
+pub fn gen_grid_graph<T>(seed: T, width: usize, height: usize) -> ListGraph
+where
+    T: Into<Option<u64>>,
+{
+    let mut rng = match seed.into() {
+        Some(seed) => WyRand::new_seed(seed),
+        None => WyRand::new(),
+    };
+
+    let mut list = vec![vec![]; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let v = y * width + x;
+            if x + 1 < width {
+                list[v].push(Edge(rng.generate_range(1..100), v + 1));
+            }
+            if x > 0 {
+                list[v].push(Edge(rng.generate_range(1..100), v - 1));
+            }
+            if y + 1 < height {
+                list[v].push(Edge(rng.generate_range(1..100), v + width));
+            }
+            if y > 0 {
+                list[v].push(Edge(rng.generate_range(1..100), v - width));
+            }
+        }
+    }
+
+    ListGraph::from(list)
+}
+
+/// Manhattan distance heuristic towards `target` on a grid from
+/// [`gen_grid_graph`]
+pub fn manhattan(width: usize, target: usize) -> impl Fn(usize) -> u32 {
+    let (tx, ty) = (target % width, target / width);
+    move |v| (tx.abs_diff(v % width) + ty.abs_diff(v / width)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{djikstra_bheap_list_graph, gen_graph, graph::ListGraph};
+
+    use super::{
+        a_star, bidirectional_djikstra, djikstra_early_exit, gen_grid_graph, manhattan, PathInfo,
+    };
+
+    /// checks the path is made of real edges and adds up to the distance
+    fn assert_valid_path(graph: &ListGraph, info: &PathInfo, src: usize, target: usize) {
+        let path = info.path.as_ref().unwrap();
+        assert_eq!(path.first(), Some(&src));
+        assert_eq!(path.last(), Some(&target));
+
+        let cost = path
+            .windows(2)
+            .map(|e| {
+                graph
+                    .neighbours(e[0])
+                    .iter()
+                    .filter(|edge| edge.1 == e[1])
+                    .map(|edge| edge.0)
+                    .min()
+                    .expect("path uses an edge that does not exist")
+            })
+            .sum::<u32>();
+        assert_eq!(Some(cost), info.distance);
+    }
+
+    #[test]
+    fn matches_full_djikstra() {
+        let graph = ListGraph::from(gen_graph(42069, 100, 600));
+
+        for src in [0, 17, 99] {
+            let full = djikstra_bheap_list_graph(graph.clone(), src);
+
+            for target in 0..100 {
+                let expected = Some(full.distance[target]).filter(|d| *d != u32::MAX);
+                let infos = [
+                    djikstra_early_exit(&graph, src, target),
+                    bidirectional_djikstra(&graph, src, target),
+                    a_star(&graph, src, target, |_| 0),
+                ];
+
+                for info in infos {
+                    assert_eq!(info.distance, expected);
+                    match expected {
+                        Some(_) => assert_valid_path(&graph, &info, src, target),
+                        None => assert_eq!(info.path, None),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn search_space_reduction() {
+        let (width, height) = (50, 50);
+        let graph = gen_grid_graph(42069, width, height);
+        let (src, target) = (width * (height / 2), width * (height / 2) + width - 1);
+
+        let early_exit = djikstra_early_exit(&graph, src, target);
+        let bidirectional = bidirectional_djikstra(&graph, src, target);
+        let a_star = a_star(&graph, src, target, manhattan(width, target));
+
+        assert_eq!(early_exit.distance, bidirectional.distance);
+        assert_eq!(early_exit.distance, a_star.distance);
+        assert_valid_path(&graph, &bidirectional, src, target);
+        assert_valid_path(&graph, &a_star, src, target);
+
+        assert!(early_exit.settled <= width * height);
+        assert!(bidirectional.settled < early_exit.settled);
+        assert!(a_star.settled <= early_exit.settled);
+    }
+
+    #[test]
+    fn same_vertex() {
+        let graph = gen_grid_graph(42069, 3, 3);
+        for info in [djikstra_early_exit(&graph, 4, 4), bidirectional_djikstra(&graph, 4, 4)] {
+            assert_eq!(info.path, Some(vec![4]));
+            assert_eq!(info.distance, Some(0));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use sc2001::{graph::Edge, priority_queue::PriorityQueue};
+
+/// Number of buckets, one for keys equal to the last popped key and one per
+/// bit position where a key can first differ from it
+const BUCKETS: usize = u32::BITS as usize + 1;
+
+/// Monotone priority queue for `Edge<u32>`.
+///
+/// Every pushed weight must be at least the last popped weight, which holds
+/// for Djikstra since the distance of a queued vertex is never smaller than
+/// the distance of the vertex being visited. Bucket `i` holds the edges
+/// whose weight first differs from the last popped weight at bit `i - 1`,
+/// so only the first non-empty bucket has to be scanned and redistributed.
+///
+/// # Time Complexity
+/// - push: O(1)
+/// - pop: O(lgC) amortised where C is the largest weight
+#[derive(Debug, Clone)]
+pub struct RadixHeap {
+    buckets: Vec<Vec<Edge<u32>>>,
+    last: u32,
+    len: usize,
+}
+
+impl Default for RadixHeap {
+    fn default() -> Self {
+        Self {
+            buckets: vec![vec![]; BUCKETS],
+            last: 0,
+            len: 0,
+        }
+    }
+}
+
+impl RadixHeap {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bucket(&self, weight: u32) -> usize {
+        (u32::BITS - (weight ^ self.last).leading_zeros()) as usize
+    }
+
+    /// # Panics
+    /// If the weight is smaller than the last popped weight
+    pub fn push(&mut self, item: Edge<u32>) {
+        assert!(item.0 >= self.last, "radix heap is monotone");
+        let i = self.bucket(item.0);
+        self.buckets[i].push(item);
+        self.len += 1;
+    }
+
+    /// Removes the edge with the smallest weight
+    pub fn pop(&mut self) -> Option<Edge<u32>> {
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty())?;
+
+            // the new minimum becomes `last`, every other edge in this
+            // bucket now differs from it at a lower bit
+            let bucket = std::mem::take(&mut self.buckets[i]);
+            self.last = bucket.iter().map(|Edge(w, _)| *w).min()?;
+            for item in bucket {
+                let j = self.bucket(item.0);
+                self.buckets[j].push(item);
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+impl PriorityQueue<Edge<u32>> for RadixHeap {
+    fn push(&mut self, item: Edge<u32>) {
+        RadixHeap::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<Edge<u32>> {
+        RadixHeap::pop(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        RadixHeap::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BinaryHeap;
+
+    use nanorand::{Rng, WyRand};
+    use sc2001::graph::Edge;
+
+    use super::RadixHeap;
+
+    #[test]
+    fn pop_in_weight_order() {
+        let mut heap = RadixHeap::default();
+        for e in [Edge(9, 0), Edge(4, 1), Edge(7, 2), Edge(0, 3), Edge(4, 4)] {
+            heap.push(e);
+        }
+
+        assert_eq!(heap.pop(), Some(Edge(0, 3)));
+        heap.push(Edge(5, 5));
+
+        let mut weights = vec![];
+        while let Some(Edge(w, _)) = heap.pop() {
+            weights.push(w);
+        }
+
+        assert_eq!(weights, vec![4, 4, 5, 7, 9]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn random_monotone_ops() {
+        let mut rng = WyRand::new_seed(42069);
+        let mut heap = RadixHeap::default();
+        let mut model = BinaryHeap::new();
+        let mut last = 0;
+
+        for _ in 0..10000 {
+            if rng.generate_range(0..3u8) == 0 {
+                let popped = heap.pop().map(|Edge(w, _)| w);
+                let expected = model.pop().map(|Edge(w, _)| w);
+                assert_eq!(popped, expected);
+                last = popped.unwrap_or(last);
+            } else {
+                // keep the sequence monotone like djikstra does
+                let e = Edge(last + rng.generate_range(0..100u32), rng.generate_range(0..100));
+                heap.push(e);
+                model.push(e);
+            }
+        }
+
+        while let Some(Edge(w, _)) = model.pop() {
+            assert_eq!(heap.pop().map(|Edge(w, _)| w), Some(w));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_below_last() {
+        let mut heap = RadixHeap::default();
+        heap.push(Edge(10, 0));
+        heap.pop();
+        heap.push(Edge(9, 0));
+    }
+}
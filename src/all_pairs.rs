@@ -0,0 +1,344 @@
+use std::ops::Sub;
+
+use num_traits::{Bounded, CheckedAdd, Zero};
+
+use crate::{
+    bellman_ford::{bellman_ford, NegativeCycle},
+    djikstra::djikstra,
+    graph::{AdjList, AdjMatrix, Edge, Graph},
+};
+
+/// Result of an all pairs shortest path run.
+///
+/// `distance[u][v]` is `W::max_value()` if `v` cannot be reached from `u`.
+/// `next[u][v]` is the vertex after `u` on a shortest path from `u` to `v`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllPairsInfo<W> {
+    pub distance: Vec<Vec<W>>,
+    pub next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W> AllPairsInfo<W> {
+    pub fn new(distance: Vec<Vec<W>>, next: Vec<Vec<Option<usize>>>) -> Self {
+        Self { distance, next }
+    }
+
+    /// Vertices on a shortest path from `u` to `v`, both ends included.
+    /// `None` if `v` cannot be reached from `u`
+    pub fn path(&self, mut u: usize, v: usize) -> Option<Vec<usize>> {
+        self.next[u][v]?;
+
+        let mut path = vec![u];
+        while u != v {
+            u = self.next[u][v]?;
+            path.push(u);
+        }
+
+        Some(path)
+    }
+}
+
+/// Floyd-Warshall on an adjacency matrix where `0` means there is no edge.
+///
+/// Negative edges are allowed. If the graph has a negative cycle the
+/// distances are undefined and the cycle is returned instead.
+///
+/// # Time Complexity
+/// - O(V^3)
+///
+/// # Space Complexity
+/// - O(V^2)
+pub fn floyd_warshall<W>(graph: &Graph<AdjMatrix<W>>) -> Result<AllPairsInfo<W>, NegativeCycle>
+where
+    W: Copy + Ord + Zero + Bounded + CheckedAdd,
+{
+    let sz_v = graph.len();
+    let mut distance = vec![vec![W::max_value(); sz_v]; sz_v];
+    let mut next = vec![vec![None::<usize>; sz_v]; sz_v];
+
+    for (u, row) in graph.internal_repr.0.iter().enumerate() {
+        for (v, weight) in row.iter().enumerate() {
+            if *weight != W::zero() {
+                distance[u][v] = *weight;
+                next[u][v] = Some(v);
+            }
+        }
+
+        // a negative self loop is a negative cycle on its own
+        distance[u][u] = distance[u][u].min(W::zero());
+        next[u][u] = Some(u);
+    }
+
+    for k in 0..sz_v {
+        for u in 0..sz_v {
+            if distance[u][k] == W::max_value() {
+                continue;
+            }
+
+            for v in 0..sz_v {
+                if distance[k][v] == W::max_value() {
+                    continue;
+                }
+
+                // around a negative cycle the distances can shrink exponentially,
+                // saturate instead of overflowing
+                let through_k = distance[u][k]
+                    .checked_add(&distance[k][v])
+                    .unwrap_or_else(|| {
+                        if distance[u][k] < W::zero() {
+                            W::min_value()
+                        } else {
+                            W::max_value()
+                        }
+                    });
+                if through_k < distance[u][v] {
+                    distance[u][v] = through_k;
+                    next[u][v] = next[u][k];
+                }
+            }
+        }
+    }
+
+    // u can reach a negative cycle if it has a negative path back to itself,
+    // let bellman ford dig the cycle out since next[u][u] may not lead to it
+    if let Some(u) = (0..sz_v).find(|u| distance[*u][*u] < W::zero()) {
+        bellman_ford(graph, u)?;
+        unreachable!("negative cycle is reachable from {u}");
+    }
+
+    Ok(AllPairsInfo::new(distance, next))
+}
+
+/// Johnson's algorithm on an adjacency list.
+///
+/// Bellman-Ford from a virtual vertex with a zero weight edge to every
+/// vertex gives a potential `h` such that `w(u, v) + h(u) - h(v)` is never
+/// negative, so Djikstra can then be run from every vertex on the reweighted
+/// graph. Returns the negative cycle instead if there is one.
+///
+/// # Time Complexity
+/// - O(VE + V(V + E)lgV), better than Floyd-Warshall on sparse graphs
+pub fn johnson<W>(graph: &Graph<AdjList<Edge<W>>>) -> Result<AllPairsInfo<W>, NegativeCycle>
+where
+    W: Copy + Ord + Zero + Bounded + Sub<Output = W>,
+{
+    let sz_v = graph.len();
+
+    // the virtual vertex is sz_v, a negative cycle never goes through it
+    // since it has no incoming edges
+    let mut augmented = graph.internal_repr.0.clone();
+    augmented.push((0..sz_v).map(|v| Edge(W::zero(), v)).collect());
+    let augmented: Graph<AdjList<Edge<W>>> = Graph::from(augmented);
+    let h = bellman_ford(&augmented, sz_v)?.distance;
+
+    let reweighted: Graph<AdjList<Edge<W>>> = Graph::from(
+        graph
+            .internal_repr
+            .0
+            .iter()
+            .enumerate()
+            .map(|(u, edges)| {
+                edges
+                    .iter()
+                    .map(|Edge(w, v)| Edge(*w + h[u] - h[*v], *v))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let mut distance = Vec::with_capacity(sz_v);
+    let mut next = Vec::with_capacity(sz_v);
+
+    for u in 0..sz_v {
+        let info = djikstra(&reweighted, u);
+
+        // undo the reweighting, the potentials cancel out along a path
+        let row = info
+            .distance
+            .iter()
+            .zip(&h)
+            .map(|(d, h_v)| {
+                if *d == W::max_value() {
+                    W::max_value()
+                } else {
+                    *d - h[u] + *h_v
+                }
+            })
+            .collect::<Vec<_>>();
+
+        distance.push(row);
+        next.push(first_hops(&info.predecessors, u));
+    }
+
+    Ok(AllPairsInfo::new(distance, next))
+}
+
+/// Turns a shortest path tree rooted at `src` into the first hop from `src`
+/// towards every vertex
+fn first_hops(predecessors: &[Option<usize>], src: usize) -> Vec<Option<usize>> {
+    let mut hops = vec![None; predecessors.len()];
+    hops[src] = Some(src);
+
+    for v in 0..predecessors.len() {
+        // climb until a vertex whose first hop is known or a child of src
+        let mut stack = vec![];
+        let mut curr = v;
+        while hops[curr].is_none() {
+            match predecessors[curr] {
+                Some(p) if p == src => {
+                    hops[curr] = Some(curr);
+                }
+                Some(p) => {
+                    stack.push(curr);
+                    curr = p;
+                }
+                None => break,
+            }
+        }
+
+        let hop = hops[curr];
+        for x in stack {
+            hops[x] = hop;
+        }
+    }
+
+    hops
+}
+
+#[cfg(test)]
+mod tests {
+    use nanorand::{Rng, WyRand};
+
+    use crate::{
+        bellman_ford::NegativeCycle,
+        graph::{AdjList, AdjMatrix, Edge, Graph},
+    };
+
+    use super::{floyd_warshall, johnson};
+
+    type ListGraph = Graph<AdjList<Edge<i64>>>;
+    type MatrixGraph = Graph<AdjMatrix<i64>>;
+
+    fn list_from_matrix(graph: &MatrixGraph) -> ListGraph {
+        ListGraph::from(
+            graph
+                .internal_repr
+                .0
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, w)| **w != 0)
+                        .map(|(v, w)| Edge(*w, v))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn negative_edges() {
+        // CLRS fig 25.1
+        let graph = MatrixGraph::from(vec![
+            vec![0, 3, 8, 0, -4],
+            vec![0, 0, 0, 1, 7],
+            vec![0, 4, 0, 0, 0],
+            vec![2, 0, -5, 0, 0],
+            vec![0, 0, 0, 6, 0],
+        ]);
+
+        let expected = vec![
+            vec![0, 1, -3, 2, -4],
+            vec![3, 0, -4, 1, -1],
+            vec![7, 4, 0, 5, 3],
+            vec![2, -1, -5, 0, -2],
+            vec![8, 5, 1, 6, 0],
+        ];
+
+        let fw = floyd_warshall(&graph).unwrap();
+        let jo = johnson(&list_from_matrix(&graph)).unwrap();
+
+        assert_eq!(fw.distance, expected);
+        assert_eq!(jo.distance, expected);
+        assert_eq!(fw.path(0, 1), Some(vec![0, 4, 3, 2, 1]));
+        assert_eq!(jo.path(0, 1), Some(vec![0, 4, 3, 2, 1]));
+        assert_eq!(fw.path(2, 2), Some(vec![2]));
+    }
+
+    #[test]
+    fn unreachable() {
+        let graph = MatrixGraph::from(vec![vec![0, 2, 0], vec![0, 0, 0], vec![0, 5, 0]]);
+
+        for info in [floyd_warshall(&graph).unwrap(), johnson(&list_from_matrix(&graph)).unwrap()] {
+            assert_eq!(info.distance[0], vec![0, 2, i64::MAX]);
+            assert_eq!(info.distance[1], vec![i64::MAX, 0, i64::MAX]);
+            assert_eq!(info.path(0, 2), None);
+            assert_eq!(info.path(2, 1), Some(vec![2, 1]));
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let graph = MatrixGraph::from(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 2, 0],
+            vec![0, 0, 0, -4],
+            vec![0, 1, 0, 0],
+        ]);
+
+        let check = |NegativeCycle(mut cycle): NegativeCycle| {
+            let min = cycle.iter().enumerate().min_by_key(|(_, v)| **v).unwrap().0;
+            cycle.rotate_left(min);
+            assert_eq!(cycle, vec![1, 2, 3]);
+        };
+
+        check(floyd_warshall(&graph).unwrap_err());
+        check(johnson(&list_from_matrix(&graph)).unwrap_err());
+    }
+
+    #[test]
+    fn negative_cycle_no_overflow() {
+        // every pair is a negative cycle, the distances blow up well past
+        // i32::MIN before the outer loop is done
+        let v = 20;
+        let mut matrix = vec![vec![-1i32; v]; v];
+        for (u, row) in matrix.iter_mut().enumerate() {
+            row[u] = 0;
+        }
+
+        let graph = Graph::<AdjMatrix<i32>>::from(matrix);
+        let NegativeCycle(cycle) = floyd_warshall(&graph).unwrap_err();
+        assert!(cycle.len() >= 2);
+    }
+
+    #[test]
+    fn floyd_warshall_matches_johnson() {
+        let mut rng = WyRand::new_seed(42069);
+
+        for _ in 0..20 {
+            let v = 30;
+            let mut matrix = vec![vec![0i64; v]; v];
+            for _ in 0..200 {
+                let (i, j) = (rng.generate_range(0..v), rng.generate_range(0..v));
+                if i != j {
+                    matrix[i][j] = rng.generate_range(1..100i64);
+                }
+            }
+
+            let graph = MatrixGraph::from(matrix);
+            let fw = floyd_warshall(&graph).unwrap();
+            let jo = johnson(&list_from_matrix(&graph)).unwrap();
+            assert_eq!(fw.distance, jo.distance);
+
+            // paths may differ on ties but must have the same length
+            for u in 0..v {
+                for t in 0..v {
+                    let cost = |path: Vec<usize>| -> i64 {
+                        path.windows(2).map(|e| graph.internal_repr.0[e[0]][e[1]]).sum()
+                    };
+                    assert_eq!(fw.path(u, t).map(cost), jo.path(u, t).map(cost));
+                }
+            }
+        }
+    }
+}
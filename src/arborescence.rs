@@ -0,0 +1,335 @@
+use std::{collections::VecDeque, ops::Sub};
+
+use num_traits::Zero;
+
+use crate::{
+    graph::{AdjList, Edge, Graph},
+    predecessor_tree::PredecessorTree,
+};
+
+type ListGraph<W> = Graph<AdjList<Edge<W>>>;
+
+/// Minimum spanning arborescence, a directed spanning tree where every
+/// vertex can be reached from the root
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arborescence<W> {
+    pub predecessors: Vec<Option<usize>>,
+    /// weight of the edge from the parent of each vertex, zero for the root
+    pub weights: Vec<W>,
+    pub cost: W,
+}
+
+impl<W> Arborescence<W> {
+    pub fn new(predecessors: Vec<Option<usize>>, weights: Vec<W>, cost: W) -> Self {
+        Self {
+            predecessors,
+            weights,
+            cost,
+        }
+    }
+}
+
+impl<W: Copy> PredecessorTree<W> for Arborescence<W> {
+    fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    fn contains(&self, vertex: usize) -> bool {
+        vertex < self.predecessors.len()
+    }
+
+    fn edge_weight(&self, vertex: usize) -> W {
+        self.weights[vertex]
+    }
+}
+
+/// Vertices that cannot be reached from the root, so no arborescence exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreachable(pub Vec<usize>);
+
+/// `(from, to, weight, id)` where `id` is the index of the edge one level up,
+/// or into the original edge list at the top level
+type LevelEdge<W> = (usize, usize, W, usize);
+
+/// Minimum arborescence rooted at `root` with Chu-Liu/Edmonds.
+///
+/// Unlike `kruskal` and `prims` the graph is read as directed. Every vertex
+/// other than the root takes its cheapest incoming edge, if that makes a
+/// cycle the cycle is contracted into one vertex and the edges entering it
+/// are reweighted by what they would save, then the whole thing repeats on
+/// the smaller graph. Self loops are ignored.
+///
+/// # Time Complexity
+/// - O(VE)
+pub fn chu_liu_edmonds<W>(graph: &ListGraph<W>, root: usize) -> Result<Arborescence<W>, Unreachable>
+where
+    W: Copy + Ord + Zero + Sub<Output = W>,
+{
+    let sz_v = graph.len();
+
+    // reachability decides whether an answer exists at all, so the
+    // contraction below never runs out of incoming edges
+    let mut reached = vec![false; sz_v];
+    reached[root] = true;
+    let mut queue = VecDeque::from([root]);
+    while let Some(v) = queue.pop_front() {
+        for Edge(_, to) in graph.neighbours(v) {
+            if !reached[*to] {
+                reached[*to] = true;
+                queue.push_back(*to);
+            }
+        }
+    }
+
+    let unreachable = (0..sz_v).filter(|v| !reached[*v]).collect::<Vec<_>>();
+    if !unreachable.is_empty() {
+        return Err(Unreachable(unreachable));
+    }
+
+    let mut edges = vec![];
+    for from in 0..sz_v {
+        for Edge(weight, to) in graph.neighbours(from) {
+            if from != *to {
+                edges.push((from, *to, *weight, edges.len()));
+            }
+        }
+    }
+
+    let mut predecessors = vec![None; sz_v];
+    let mut weights = vec![W::zero(); sz_v];
+    let mut cost = W::zero();
+    for i in contract(sz_v, root, &edges) {
+        let (from, to, weight, _) = edges[i];
+        predecessors[to] = Some(from);
+        weights[to] = weight;
+        cost = cost + weight;
+    }
+
+    Ok(Arborescence::new(predecessors, weights, cost))
+}
+
+/// Returns the indices into `edges` of the chosen edge into every vertex
+/// other than `root`
+fn contract<W>(sz_v: usize, root: usize, edges: &[LevelEdge<W>]) -> Vec<usize>
+where
+    W: Copy + Ord + Zero + Sub<Output = W>,
+{
+    // cheapest incoming edge of every vertex
+    let mut best = vec![None::<usize>; sz_v];
+    for (i, &(_, to, weight, _)) in edges.iter().enumerate() {
+        if to != root && best[to].is_none_or(|b| weight < edges[b].2) {
+            best[to] = Some(i);
+        }
+    }
+
+    // follow the cheapest edges backwards from every vertex, a walk that
+    // comes back onto itself has found a cycle
+    let mut comp = vec![None::<usize>; sz_v];
+    let mut on_walk = vec![None::<usize>; sz_v];
+    let mut in_cycle = vec![false; sz_v];
+    let mut sz_comp = 0;
+
+    for start in 0..sz_v {
+        let mut v = start;
+        while v != root && comp[v].is_none() && on_walk[v].is_none() {
+            on_walk[v] = Some(start);
+            v = edges[best[v].expect("every vertex is reachable")].0;
+        }
+
+        if v != root && comp[v].is_none() && on_walk[v] == Some(start) {
+            let mut x = v;
+            loop {
+                comp[x] = Some(sz_comp);
+                in_cycle[x] = true;
+                x = edges[best[x].unwrap()].0;
+                if x == v {
+                    break;
+                }
+            }
+            sz_comp += 1;
+        }
+    }
+
+    // no cycle, the cheapest edges already form the arborescence
+    if sz_comp == 0 {
+        return best.into_iter().flatten().collect();
+    }
+
+    let comp = comp
+        .into_iter()
+        .map(|c| {
+            c.unwrap_or_else(|| {
+                sz_comp += 1;
+                sz_comp - 1
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // entering a cycle at `to` means dropping the cycle edge into `to`, so
+    // the edge only costs what it adds on top of that one
+    let contracted = edges
+        .iter()
+        .enumerate()
+        .filter(|(_, (from, to, _, _))| comp[*from] != comp[*to])
+        .map(|(i, &(from, to, weight, _))| {
+            let weight = if in_cycle[to] {
+                weight - edges[best[to].unwrap()].2
+            } else {
+                weight
+            };
+            (comp[from], comp[to], weight, i)
+        })
+        .collect::<Vec<_>>();
+
+    let chosen = contract(sz_comp, comp[root], &contracted)
+        .into_iter()
+        .map(|i| contracted[i].3)
+        .collect::<Vec<_>>();
+
+    // expand the cycles again, every cycle vertex keeps its cheapest edge
+    // except the one the cycle is entered through
+    let mut entered = vec![false; sz_v];
+    for &i in &chosen {
+        entered[edges[i].1] = true;
+    }
+
+    let kept = (0..sz_v).filter(|v| in_cycle[*v] && !entered[*v]).map(|v| best[v].unwrap());
+    chosen.iter().copied().chain(kept).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nanorand::{Rng, WyRand};
+
+    use crate::{
+        graph::Edge, kruskal::kruskal, predecessor_tree::PredecessorTree,
+        test_utils::gen_connected_graph,
+    };
+
+    use super::{chu_liu_edmonds, ListGraph, Unreachable};
+
+    /// tries every way of picking one incoming edge per vertex
+    fn brute_force(graph: &ListGraph<u32>, root: usize) -> Option<u32> {
+        let sz_v = graph.len();
+        let mut incoming = vec![vec![]; sz_v];
+        for from in 0..sz_v {
+            for Edge(w, to) in graph.neighbours(from) {
+                if *to != root && *to != from {
+                    incoming[*to].push((from, *w));
+                }
+            }
+        }
+
+        if (0..sz_v).any(|v| v != root && incoming[v].is_empty()) {
+            return None;
+        }
+
+        let mut best = None;
+        let mut choice = vec![0; sz_v];
+        loop {
+            // parents must lead back to the root from every vertex
+            let parent = |v: usize| incoming[v][choice[v]].0;
+            let valid = (0..sz_v).filter(|v| *v != root).all(|v| {
+                let mut x = v;
+                for _ in 0..sz_v {
+                    if x == root {
+                        return true;
+                    }
+                    x = parent(x);
+                }
+                false
+            });
+
+            if valid {
+                let cost = (0..sz_v)
+                    .filter(|v| *v != root)
+                    .map(|v| incoming[v][choice[v]].1)
+                    .sum::<u32>();
+                best = Some(best.map_or(cost, |b: u32| b.min(cost)));
+            }
+
+            // next combination
+            let mut v = 0;
+            loop {
+                if v == sz_v {
+                    return best;
+                }
+                if v == root {
+                    v += 1;
+                    continue;
+                }
+                choice[v] += 1;
+                if choice[v] < incoming[v].len() {
+                    break;
+                }
+                choice[v] = 0;
+                v += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn contracts_cycle() {
+        // the cheapest incoming edges form the cycle 1 -> 2 -> 3 -> 1 which
+        // is cheapest to enter at 1, dropping 3 -> 1
+        let graph = ListGraph::from([
+            vec![Edge(10, 1), Edge(12, 2), Edge(20, 3)],
+            vec![Edge(1, 2)],
+            vec![Edge(2, 1), Edge(3, 3)],
+            vec![Edge(1, 1)],
+        ]);
+
+        let arb = chu_liu_edmonds(&graph, 0).unwrap();
+        assert_eq!(arb.cost, 14);
+        assert_eq!(arb.predecessors, vec![None, Some(0), Some(1), Some(2)]);
+        assert_eq!(arb.path_to(3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(arb.tree_edges().map(|(_, Edge(w, _))| w).sum::<u32>(), arb.cost);
+    }
+
+    #[test]
+    fn unreachable() {
+        let graph = ListGraph::from([vec![Edge(1, 1)], vec![], vec![Edge(1, 0)], vec![Edge(1, 2)]]);
+        assert_eq!(chu_liu_edmonds(&graph, 0), Err(Unreachable(vec![2, 3])));
+        assert_eq!(chu_liu_edmonds(&graph, 3).unwrap().cost, 3);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = WyRand::new_seed(42069);
+
+        for _ in 0..200 {
+            let v = 6;
+            let mut list = vec![vec![]; v];
+            for _ in 0..14 {
+                let (from, to) = (rng.generate_range(0..v), rng.generate_range(0..v));
+                list[from].push(Edge(rng.generate_range(1..20u32), to));
+            }
+
+            let graph = ListGraph::from(list);
+            let root = rng.generate_range(0..v);
+            let expected = brute_force(&graph, root);
+
+            match chu_liu_edmonds(&graph, root) {
+                Ok(arb) => {
+                    assert_eq!(Some(arb.cost), expected);
+                    assert!(arb.depths().iter().all(|d| d.is_some()));
+                    for (from, edge) in arb.tree_edges() {
+                        assert!(graph.neighbours(from).contains(&edge));
+                    }
+                }
+                Err(_) => assert_eq!(expected, None),
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_graph_matches_mst() {
+        // with both directions present any spanning tree can be pointed away
+        // from the root, so the cheapest arborescence is the mst
+        for seed in 0..10 {
+            let graph = gen_connected_graph(seed, 80, 300);
+            let mst = kruskal(graph.clone()).unwrap();
+            assert_eq!(chu_liu_edmonds(&graph, 0).unwrap().cost, mst.cost);
+        }
+    }
+}
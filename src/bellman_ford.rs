@@ -0,0 +1,178 @@
+use num_traits::{Bounded, Zero};
+
+use crate::graph::{Edge, WeightedGraph};
+
+/// Result of a Bellman-Ford run.
+///
+/// Like [`DjikstraRunInfo`](crate::djikstra::DjikstraRunInfo), unreachable
+/// vertices keep a distance of `W::max_value()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BellmanFordRunInfo<W> {
+    pub distance: Vec<W>,
+    pub predecessors: Vec<Option<usize>>,
+}
+
+/// Vertices of a negative cycle reachable from the source, in the order the
+/// edges of the cycle go. The last vertex has an edge back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle(pub Vec<usize>);
+
+impl<W> BellmanFordRunInfo<W> {
+    pub fn new(distance: Vec<W>, predecessors: Vec<Option<usize>>) -> Self {
+        Self {
+            distance,
+            predecessors,
+        }
+    }
+}
+
+/// Single source shortest paths allowing negative edge weights.
+///
+/// Returns the vertices of a negative cycle instead if one is reachable from
+/// `src`, since the shortest distances are then undefined. Cycles that
+/// cannot be reached from `src` do not matter.
+///
+/// Works on `Graph<AdjList<Edge<i64>>>` as well as signed matrices, where
+/// `0` still means there is no edge.
+///
+/// # Time Complexity
+/// - O(VE), stops early once a round relaxes nothing
+pub fn bellman_ford<W, G>(graph: &G, src: usize) -> Result<BellmanFordRunInfo<W>, NegativeCycle>
+where
+    W: Copy + Ord + Zero + Bounded,
+    G: WeightedGraph<W>,
+{
+    let sz_v = graph.vertex_count();
+    if sz_v == 0 {
+        return Ok(BellmanFordRunInfo::new(vec![], vec![]));
+    }
+
+    let mut distance = vec![W::max_value(); sz_v];
+    let mut predecessors = vec![None::<usize>; sz_v];
+    distance[src] = W::zero();
+
+    // a shortest path has at most |V| - 1 edges, so |V| - 1 rounds suffice
+    // the |V|th round only relaxes something if there is a negative cycle
+    let mut last_relaxed = None;
+    for _ in 0..sz_v {
+        last_relaxed = None;
+
+        for vertex in 0..sz_v {
+            // cant relax from a vertex we have not reached yet
+            if distance[vertex] == W::max_value() {
+                continue;
+            }
+
+            for Edge(weight, neighbour) in graph.edges(vertex) {
+                if distance[vertex] + weight < distance[neighbour] {
+                    distance[neighbour] = distance[vertex] + weight;
+                    predecessors[neighbour] = Some(vertex);
+                    last_relaxed = Some(neighbour);
+                }
+            }
+        }
+
+        if last_relaxed.is_none() {
+            return Ok(BellmanFordRunInfo::new(distance, predecessors));
+        }
+    }
+
+    // still relaxing in the |V|th round, walking back |V| predecessors from
+    // the last relaxed vertex is guaranteed to land on the cycle
+    let mut curr = last_relaxed.unwrap();
+    for _ in 0..sz_v {
+        curr = predecessors[curr].expect("vertex on a negative cycle has a predecessor");
+    }
+
+    let mut cycle = vec![curr];
+    let mut v = predecessors[curr].unwrap();
+    while v != curr {
+        cycle.push(v);
+        v = predecessors[v].unwrap();
+    }
+
+    // predecessors point backwards along the cycle
+    cycle.reverse();
+    Err(NegativeCycle(cycle))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{AdjList, AdjMatrix, Edge, Graph};
+
+    use super::{bellman_ford, NegativeCycle};
+
+    type ListGraph = Graph<AdjList<Edge<i64>>>;
+    type MatrixGraph = Graph<AdjMatrix<i64>>;
+
+    /// rotates the cycle so that it starts from its smallest vertex
+    fn normalise(NegativeCycle(mut cycle): NegativeCycle) -> Vec<usize> {
+        let min = cycle.iter().enumerate().min_by_key(|(_, v)| **v).unwrap().0;
+        cycle.rotate_left(min);
+        cycle
+    }
+
+    #[test]
+    fn negative_edges() {
+        let graph = ListGraph::from([
+            vec![Edge(6, 1), Edge(7, 3)],
+            vec![Edge(5, 2), Edge(8, 3), Edge(-4, 4)],
+            vec![Edge(-2, 1)],
+            vec![Edge(-3, 2), Edge(9, 4)],
+            vec![Edge(2, 0), Edge(7, 2)],
+        ]);
+
+        let info = bellman_ford(&graph, 0).unwrap();
+        assert_eq!(info.distance, vec![0, 2, 4, 7, -2]);
+        assert_eq!(info.predecessors, vec![None, Some(2), Some(3), Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn unreachable() {
+        let graph = MatrixGraph::from(vec![
+            vec![0, 24, 0, 0],
+            vec![1, 0, 0, -5],
+            vec![0, 0, 0, 3],
+            vec![0, 0, 0, 0],
+        ]);
+
+        let info = bellman_ford(&graph, 0).unwrap();
+        assert_eq!(info.distance, vec![0, 24, i64::MAX, 19]);
+        assert_eq!(info.predecessors[2], None);
+
+        let info = bellman_ford(&MatrixGraph::from(vec![vec![0; 3]; 3]), 0).unwrap();
+        assert_eq!(info.distance, vec![0, i64::MAX, i64::MAX]);
+
+        let info = bellman_ford(&ListGraph::from(Vec::<Vec<Edge<i64>>>::new()), 0).unwrap();
+        assert!(info.distance.is_empty());
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let graph = ListGraph::from([
+            vec![Edge(1, 1)],
+            vec![Edge(1, 2)],
+            vec![Edge(-1, 3)],
+            vec![Edge(-1, 1), Edge(1, 4)],
+            vec![],
+        ]);
+
+        let cycle = bellman_ford(&graph, 0).unwrap_err();
+        assert_eq!(normalise(cycle), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unreachable_negative_cycle() {
+        // 2 <-> 3 is a negative cycle but it cant be reached from 0
+        let graph = MatrixGraph::from(vec![
+            vec![0, 4, 0, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, -3],
+            vec![0, 1, 1, 0],
+        ]);
+
+        let info = bellman_ford(&graph, 0).unwrap();
+        assert_eq!(info.distance, vec![0, 4, i64::MAX, i64::MAX]);
+        assert_eq!(normalise(bellman_ford(&graph, 2).unwrap_err()), vec![2, 3]);
+    }
+}
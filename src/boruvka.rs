@@ -0,0 +1,199 @@
+use std::thread;
+
+use crate::{
+    graph::{AdjList, Edge, Graph},
+    kruskal::KruskalRunInfo,
+    union_find::{UnionFind, WeightedQuickUnion},
+};
+
+type ListGraph = Graph<AdjList<Edge<u32>>>;
+type Uf = UnionFind<WeightedQuickUnion, usize, Vec<usize>>;
+
+/// `(weight, from, to)` of the cheapest edge leaving each component, indexed
+/// by the root of the component
+type Cheapest = Vec<Option<(u32, usize, usize)>>;
+
+/// Minimum spanning tree with Boruvka's, `None` if the graph is not
+/// connected. The result has the same shape as Kruskal's.
+///
+/// Every round each component picks the cheapest edge leaving it and all of
+/// them are added at once, so the number of components at least halves.
+///
+/// # Time Complexity
+/// - O(ElgV)
+pub fn boruvka(graph: ListGraph) -> Option<KruskalRunInfo<u32>> {
+    boruvka_with(&graph, |roots, cheapest| {
+        scan(&graph, roots, 0..graph.len(), cheapest)
+    })
+}
+
+/// Boruvka's with the search for the cheapest edges split across `threads`
+/// scoped threads, each scanning its own range of vertices. Merging the
+/// components is still done on one thread.
+///
+/// # Panics
+/// If `threads` is 0
+pub fn boruvka_parallel(graph: ListGraph, threads: usize) -> Option<KruskalRunInfo<u32>> {
+    assert!(threads > 0, "need at least one thread");
+    let sz_v = graph.len();
+    let chunk = sz_v.div_ceil(threads).max(1);
+
+    // one buffer per worker, reused across rounds
+    let mut locals = (0..sz_v)
+        .step_by(chunk)
+        .map(|_| vec![None; sz_v])
+        .collect::<Vec<_>>();
+
+    boruvka_with(&graph, |roots, cheapest| {
+        thread::scope(|s| {
+            let handles = (0..sz_v)
+                .step_by(chunk)
+                .zip(&mut locals)
+                .map(|(start, local)| {
+                    let graph = &graph;
+                    s.spawn(move || scan(graph, roots, start..sz_v.min(start + chunk), local))
+                })
+                .collect::<Vec<_>>();
+
+            for h in handles {
+                h.join().expect("boruvka worker panicked");
+            }
+        });
+
+        for local in &mut locals {
+            // take leaves the buffer empty for the next round
+            for (root, candidate) in local.iter_mut().enumerate() {
+                if let Some(candidate) = candidate.take() {
+                    offer(cheapest, root, candidate);
+                }
+            }
+        }
+    })
+}
+
+/// Runs the rounds, `find_cheapest` fills in the cheapest outgoing edge of
+/// every component given the root of every vertex
+fn boruvka_with<F>(graph: &ListGraph, mut find_cheapest: F) -> Option<KruskalRunInfo<u32>>
+where
+    F: FnMut(&[usize], &mut Cheapest),
+{
+    let sz_v = graph.len();
+    let mut uf = Uf::new(sz_v);
+    let mut chosen = vec![];
+    let mut cost = 0;
+
+    while uf.count > 1 {
+        let roots = (0..sz_v).map(|v| uf.find(v)).collect::<Vec<_>>();
+        let mut cheapest = vec![None; sz_v];
+        find_cheapest(&roots, &mut cheapest);
+
+        let before = uf.count;
+        for (weight, from, to) in cheapest.into_iter().flatten() {
+            // both ends may have picked the same edge
+            if !uf.connected(from, to) {
+                uf.union(from, to);
+                cost += weight;
+                chosen.push((weight, from, to));
+            }
+        }
+
+        // no component has an edge leaving it
+        if uf.count == before {
+            return None;
+        }
+    }
+
+    Some(KruskalRunInfo::new(chosen, cost, uf))
+}
+
+/// Finds the cheapest edge leaving the component of every vertex in `range`
+fn scan(graph: &ListGraph, roots: &[usize], range: std::ops::Range<usize>, cheapest: &mut Cheapest) {
+    for from in range {
+        for Edge(weight, to) in graph.neighbours(from) {
+            if roots[from] != roots[*to] {
+                offer(cheapest, roots[from], (*weight, from, *to));
+            }
+        }
+    }
+}
+
+/// Keeps the cheaper of the current and the candidate edge for `root`.
+///
+/// Ties are broken by the endpoints so every component agrees on a total
+/// order of the edges, otherwise equal weights could close a cycle.
+fn offer(cheapest: &mut Cheapest, root: usize, candidate: (u32, usize, usize)) {
+    let key = |(w, a, b): (u32, usize, usize)| (w, a.min(b), a.max(b));
+    match cheapest[root] {
+        Some(curr) if key(curr) <= key(candidate) => {}
+        _ => cheapest[root] = Some(candidate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::Edge,
+        kruskal::kruskal,
+        test_utils::gen_connected_graph,
+    };
+
+    use super::{boruvka, boruvka_parallel, ListGraph};
+
+    #[test]
+    fn boruvka_test() {
+        let adj_list = [
+            vec![Edge(10, 1), Edge(1, 2), Edge(4, 3)],
+            vec![Edge(10, 0), Edge(3, 2), Edge(1, 4)],
+            vec![Edge(1, 0), Edge(2, 3), Edge(8, 5), Edge(3, 1)],
+            vec![Edge(4, 0), Edge(2, 2), Edge(2, 5), Edge(7, 6)],
+            vec![Edge(1, 1), Edge(1, 5), Edge(8, 7)],
+            vec![Edge(8, 2), Edge(2, 3), Edge(6, 6), Edge(9, 7), Edge(1, 4)],
+            vec![Edge(7, 3), Edge(6, 5), Edge(12, 7)],
+            vec![Edge(12, 6), Edge(9, 5), Edge(8, 4)],
+        ];
+
+        let graph = ListGraph::from(adj_list);
+        let mst = boruvka(graph.clone()).unwrap();
+        assert_eq!(mst.cost, 21);
+        assert_eq!(mst.edges.len(), 7);
+        assert_eq!(boruvka_parallel(graph, 3).unwrap().cost, 21);
+    }
+
+    #[test]
+    fn boruvka_disconnected() {
+        let adj_list = [vec![Edge(2, 1)], vec![Edge(2, 0)], vec![]];
+
+        let graph = ListGraph::from(adj_list);
+        assert!(boruvka(graph.clone()).is_none(), "Should be None!");
+        assert!(boruvka_parallel(graph, 2).is_none(), "Should be None!");
+    }
+
+    #[test]
+    fn boruvka_equal_weights() {
+        // every edge has the same weight, the tie break must avoid cycles
+        let adj_list = [
+            vec![Edge(1, 1), Edge(1, 2)],
+            vec![Edge(1, 0), Edge(1, 2)],
+            vec![Edge(1, 0), Edge(1, 1)],
+        ];
+
+        let mst = boruvka(ListGraph::from(adj_list)).unwrap();
+        assert_eq!(mst.cost, 2);
+        assert_eq!(mst.edges.len(), 2);
+    }
+
+    #[test]
+    fn boruvka_matches_kruskal() {
+        for seed in 0..20 {
+            let graph = gen_connected_graph(seed, 200, 600);
+            let cost = kruskal(graph.clone()).unwrap().cost;
+
+            assert_eq!(boruvka(graph.clone()).unwrap().cost, cost);
+            for threads in [1, 2, 7] {
+                let mst = boruvka_parallel(graph.clone(), threads).unwrap();
+                assert_eq!(mst.cost, cost);
+                assert_eq!(mst.edges.len(), 199);
+            }
+        }
+    }
+}
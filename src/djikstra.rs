@@ -0,0 +1,228 @@
+use std::collections::BinaryHeap;
+
+use num_traits::{Bounded, Zero};
+
+use crate::{
+    graph::{Edge, WeightedGraph},
+    indexed_heap::IndexedMinHeap,
+    priority_queue::{IndexedPriorityQueue, PriorityQueue},
+};
+
+/// Result of a single source shortest path run.
+///
+/// Unreachable vertices keep a distance of `W::max_value()` which is
+/// functionally the same as infinity, and are never marked as visited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DjikstraRunInfo<W> {
+    pub distance: Vec<W>,
+    pub predecessors: Vec<Option<usize>>,
+    pub visited: Vec<bool>,
+}
+
+impl<W> DjikstraRunInfo<W> {
+    pub fn new(distance: Vec<W>, predecessors: Vec<Option<usize>>, visited: Vec<bool>) -> Self {
+        Self {
+            distance,
+            predecessors,
+            visited,
+        }
+    }
+}
+
+/// Djikstra using `BinaryHeap` as the priority queue
+pub fn djikstra<W, G>(graph: &G, src: usize) -> DjikstraRunInfo<W>
+where
+    W: Copy + Ord + Zero + Bounded,
+    G: WeightedGraph<W>,
+{
+    djikstra_with::<BinaryHeap<_>, _, _>(graph, src)
+}
+
+/// Djikstra with lazy deletion, generic over the priority queue `Q`.
+///
+/// A vertex may be pushed more than once, stale entries are skipped when
+/// popped since the vertex has already been visited.
+///
+/// # Time Complexity
+/// - O((V + E)lgV) with a binary heap
+/// - O(V^2 + E) with an unsorted array
+pub fn djikstra_with<Q, W, G>(graph: &G, src: usize) -> DjikstraRunInfo<W>
+where
+    Q: PriorityQueue<Edge<W>> + Default,
+    W: Copy + Ord + Zero + Bounded,
+    G: WeightedGraph<W>,
+{
+    let sz_v = graph.vertex_count();
+    if sz_v == 0 {
+        return DjikstraRunInfo::new(vec![], vec![], vec![]);
+    }
+
+    let mut distance = vec![W::max_value(); sz_v];
+    let mut predecessors = vec![None::<usize>; sz_v];
+    let mut visited = vec![false; sz_v];
+    let mut pq = Q::default();
+
+    // src distance to itself is 0
+    distance[src] = W::zero();
+    pq.push(Edge(W::zero(), src));
+
+    // get cheapest vertex
+    while let Some(Edge(_, vertex)) = pq.pop() {
+        if visited[vertex] {
+            continue;
+        }
+
+        // visit curr vertex
+        visited[vertex] = true;
+
+        // get vertex neighbours and update distance table from vertex
+        // only visit vertex that have not been visited
+        for Edge(weight, neighbour) in graph.edges(vertex) {
+            if !visited[neighbour] && distance[neighbour] > distance[vertex] + weight {
+                distance[neighbour] = distance[vertex] + weight;
+                predecessors[neighbour] = Some(vertex);
+                pq.push(Edge(distance[neighbour], neighbour));
+            }
+        }
+    }
+
+    DjikstraRunInfo::new(distance, predecessors, visited)
+}
+
+/// Djikstra using `IndexedMinHeap` with decrease key
+pub fn djikstra_eager<W, G>(graph: &G, src: usize) -> DjikstraRunInfo<W>
+where
+    W: Copy + Ord + Zero + Bounded,
+    G: WeightedGraph<W>,
+{
+    djikstra_eager_with::<IndexedMinHeap<_>, _, _>(graph, src)
+}
+
+/// Djikstra with decrease key, generic over the indexed priority queue `Q`.
+/// Every vertex is queued at most once and its distance is lowered in place.
+///
+/// # Time Complexity
+/// - O((V + E)lgV) with a binary heap, the queue never holds more than V entries
+/// - O(E + VlgV) with a fibonacci heap
+pub fn djikstra_eager_with<Q, W, G>(graph: &G, src: usize) -> DjikstraRunInfo<W>
+where
+    Q: IndexedPriorityQueue<W>,
+    W: Copy + Ord + Zero + Bounded,
+    G: WeightedGraph<W>,
+{
+    let sz_v = graph.vertex_count();
+    if sz_v == 0 {
+        return DjikstraRunInfo::new(vec![], vec![], vec![]);
+    }
+
+    let mut distance = vec![W::max_value(); sz_v];
+    let mut predecessors = vec![None::<usize>; sz_v];
+    let mut visited = vec![false; sz_v];
+    let mut pq = Q::with_vertices(sz_v);
+
+    distance[src] = W::zero();
+    pq.push(src, W::zero());
+
+    while let Some((vertex, dist)) = pq.pop_min() {
+        visited[vertex] = true;
+
+        for Edge(weight, neighbour) in graph.edges(vertex) {
+            if visited[neighbour] || distance[neighbour] <= dist + weight {
+                continue;
+            }
+
+            distance[neighbour] = dist + weight;
+            predecessors[neighbour] = Some(vertex);
+
+            if pq.contains(neighbour) {
+                pq.decrease_key(neighbour, distance[neighbour]);
+            } else {
+                pq.push(neighbour, distance[neighbour]);
+            }
+        }
+    }
+
+    DjikstraRunInfo::new(distance, predecessors, visited)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::{AdjList, AdjMatrix, Edge, Graph, WeightedGraph},
+        predecessor_tree::PredecessorTree,
+    };
+
+    use super::{djikstra, djikstra_eager};
+
+    type MatrixGraph<W> = Graph<AdjMatrix<W>>;
+    type ListGraph<W> = Graph<AdjList<Edge<W>>>;
+
+    fn to_list<W: Copy + num_traits::Zero + PartialEq>(graph: &MatrixGraph<W>) -> ListGraph<W> {
+        (0..graph.len())
+            .map(|i| graph.edges(i).collect())
+            .collect::<Vec<Vec<_>>>()
+            .into()
+    }
+
+    fn all_graphs() -> Vec<(Vec<Vec<u32>>, Vec<u32>)> {
+        vec![
+            (
+                vec![
+                    vec![0, 10, 5, 0, 0],
+                    vec![0, 0, 2, 1, 0],
+                    vec![0, 3, 0, 9, 2],
+                    vec![0, 0, 0, 0, 4],
+                    vec![0, 0, 0, 6, 0],
+                ],
+                vec![0, 8, 5, 9, 7],
+            ),
+            (
+                vec![vec![0, 24, 0], vec![1, 0, 0], vec![0, 0, 0]],
+                vec![0, 24, u32::MAX],
+            ),
+            (
+                vec![vec![0, 0, 0], vec![0, 0, 0], vec![0, 0, 0]],
+                vec![0, u32::MAX, u32::MAX],
+            ),
+            (vec![], vec![]),
+        ]
+    }
+
+    #[test]
+    fn djikstra_matrix_and_list() {
+        for (mat, res) in all_graphs() {
+            let mat_graph = MatrixGraph::from(mat);
+            let list_graph = to_list(&mat_graph);
+            assert_eq!(res, djikstra(&mat_graph, 0).distance);
+            assert_eq!(res, djikstra(&list_graph, 0).distance);
+            assert_eq!(res, djikstra_eager(&mat_graph, 0).distance);
+            assert_eq!(res, djikstra_eager(&list_graph, 0).distance);
+        }
+    }
+
+    #[test]
+    fn djikstra_path_to() {
+        let (mat, _) = all_graphs().remove(0);
+        let info = djikstra(&MatrixGraph::from(mat), 0);
+        assert_eq!(info.path_to(0), Some(vec![0]));
+        assert_eq!(info.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(info.path_to(4), Some(vec![0, 2, 4]));
+
+        let (mat, _) = all_graphs().remove(1);
+        let info = djikstra(&MatrixGraph::from(mat), 0);
+        assert_eq!(info.path_to(2), None);
+        assert_eq!(info.path_to(3), None);
+    }
+
+    #[test]
+    fn djikstra_u64_weights() {
+        let graph = ListGraph::from([
+            vec![Edge(u32::MAX as u64, 1), Edge(1, 2)],
+            vec![],
+            vec![Edge(u32::MAX as u64, 1)],
+        ]);
+        let info = djikstra(&graph, 0);
+        assert_eq!(info.distance, vec![0, u32::MAX as u64, 1]);
+        assert_eq!(info.path_to(1), Some(vec![0, 1]));
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use crate::union_find::RollbackUnionFind;
+
+/// Change to the undirected graph, edges are matched up regardless of the
+/// order of their endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    Insert(usize, usize),
+    Delete(usize, usize),
+}
+
+/// Answers connectivity queries on a graph that changes over time, all the
+/// updates and queries have to be known up front.
+///
+/// Time `t` is the graph after the first `t` updates, so it runs from 0, the
+/// empty graph, to `updates.len()`. `queries` are `(t, u, v)` and the answer
+/// to each is whether `u` and `v` are connected at time `t`, in the same
+/// order as the queries.
+///
+/// Every edge is alive over an interval of time. The intervals are put on a
+/// segment tree over time, then a walk down the tree unions the edges on each
+/// node and rolls them back on the way up, so each leaf sees exactly the
+/// edges alive at its time. An edge inserted more than once is only gone once
+/// all of its copies are deleted.
+///
+/// # Panics
+/// If an edge is deleted that is not in the graph or a query is for a time
+/// past the last update
+///
+/// # Time Complexity
+/// - O((U lgU + Q)lgV) for U updates and Q queries
+pub fn connected_at(sz_v: usize, updates: &[Update], queries: &[(usize, usize, usize)]) -> Vec<bool> {
+    let sz_t = updates.len() + 1;
+    let mut tree = SegmentTree::new(sz_t);
+
+    // start times of the copies of every edge currently in the graph
+    let mut alive = HashMap::<(usize, usize), Vec<usize>>::new();
+    for (i, update) in updates.iter().enumerate() {
+        match *update {
+            Update::Insert(u, v) => alive.entry((u.min(v), u.max(v))).or_default().push(i + 1),
+            Update::Delete(u, v) => {
+                let edge = (u.min(v), u.max(v));
+                let start = alive
+                    .get_mut(&edge)
+                    .and_then(|starts| starts.pop())
+                    .unwrap_or_else(|| panic!("deleting {:?} which is not in the graph", edge));
+                tree.insert(start, i + 1, edge);
+            }
+        }
+    }
+
+    for (edge, starts) in alive {
+        for start in starts {
+            tree.insert(start, sz_t, edge);
+        }
+    }
+
+    let mut at_time = vec![vec![]; sz_t];
+    for (i, &(t, u, v)) in queries.iter().enumerate() {
+        assert!(t < sz_t, "query at time {} but there are only {} updates", t, updates.len());
+        at_time[t].push((i, u, v));
+    }
+
+    let mut answers = vec![false; queries.len()];
+    let mut uf = RollbackUnionFind::new(sz_v);
+    tree.walk(1, 0, sz_t, &mut uf, &mut |t, uf| {
+        for &(i, u, v) in &at_time[t] {
+            answers[i] = uf.connected(u, v);
+        }
+    });
+
+    answers
+}
+
+/// Edges alive over the range of times covered by every node, node 1 is the
+/// root and node `i` has children `2i` and `2i + 1`
+struct SegmentTree {
+    sz_t: usize,
+    edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl SegmentTree {
+    fn new(sz_t: usize) -> Self {
+        Self {
+            sz_t,
+            edges: vec![vec![]; 4 * sz_t],
+        }
+    }
+
+    /// Adds `edge` as alive over the times `start..end`
+    fn insert(&mut self, start: usize, end: usize, edge: (usize, usize)) {
+        if start < end {
+            self.insert_at(1, 0, self.sz_t, start, end, edge);
+        }
+    }
+
+    fn insert_at(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize, edge: (usize, usize)) {
+        if end <= lo || hi <= start {
+            return;
+        }
+
+        // the whole range of the node is covered, its children inherit it
+        if start <= lo && hi <= end {
+            self.edges[node].push(edge);
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        self.insert_at(2 * node, lo, mid, start, end, edge);
+        self.insert_at(2 * node + 1, mid, hi, start, end, edge);
+    }
+
+    /// Calls `at_leaf` for every time in `lo..hi` with the edges alive at
+    /// that time unioned in `uf`, leaves `uf` as it was found
+    fn walk<F>(&self, node: usize, lo: usize, hi: usize, uf: &mut RollbackUnionFind, at_leaf: &mut F)
+    where
+        F: FnMut(usize, &RollbackUnionFind),
+    {
+        let snapshot = uf.snapshot();
+        for &(u, v) in &self.edges[node] {
+            uf.union(u, v);
+        }
+
+        if hi - lo == 1 {
+            at_leaf(lo, uf);
+        } else {
+            let mid = lo + (hi - lo) / 2;
+            self.walk(2 * node, lo, mid, uf, at_leaf);
+            self.walk(2 * node + 1, mid, hi, uf, at_leaf);
+        }
+
+        uf.rollback(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nanorand::{Rng, WyRand};
+
+    use crate::union_find::RollbackUnionFind;
+
+    use super::{connected_at, Update};
+
+    #[test]
+    fn insert_and_delete() {
+        let updates = [
+            Update::Insert(0, 1),
+            Update::Insert(1, 2),
+            Update::Delete(1, 0),
+            Update::Insert(2, 0),
+            Update::Insert(3, 4),
+            Update::Delete(2, 1),
+        ];
+        let queries = [(0, 0, 1), (2, 0, 2), (3, 0, 2), (4, 1, 0), (6, 0, 1), (6, 0, 2), (6, 4, 3)];
+
+        assert_eq!(
+            connected_at(5, &updates, &queries),
+            vec![false, true, false, true, false, true, true]
+        );
+    }
+
+    #[test]
+    fn duplicate_edges() {
+        // the edge stays until both copies are deleted
+        let updates = [
+            Update::Insert(0, 1),
+            Update::Insert(1, 0),
+            Update::Delete(0, 1),
+            Update::Delete(0, 1),
+        ];
+        let queries = [(2, 0, 1), (3, 0, 1), (4, 0, 1)];
+
+        assert_eq!(connected_at(2, &updates, &queries), vec![true, true, false]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn delete_missing_edge() {
+        connected_at(3, &[Update::Insert(0, 1), Update::Delete(1, 2)], &[]);
+    }
+
+    #[test]
+    fn matches_rebuild() {
+        let (n, sz_u) = (30, 400);
+        let mut rng = WyRand::new_seed(42069);
+        let mut present = vec![];
+        let mut updates = vec![];
+
+        for _ in 0..sz_u {
+            if !present.is_empty() && rng.generate_range(0..3u8) == 0 {
+                let (u, v) = present.swap_remove(rng.generate_range(0..present.len()));
+                updates.push(Update::Delete(u, v));
+            } else {
+                let (u, v) = (rng.generate_range(0..n), rng.generate_range(0..n));
+                present.push((u, v));
+                updates.push(Update::Insert(u, v));
+            }
+        }
+
+        let queries = (0..1000)
+            .map(|_| {
+                (
+                    rng.generate_range(0..=sz_u),
+                    rng.generate_range(0..n),
+                    rng.generate_range(0..n),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // replay the updates from scratch for every query
+        let expected = queries
+            .iter()
+            .map(|&(t, u, v)| {
+                let mut edges = vec![];
+                for update in &updates[..t] {
+                    match *update {
+                        Update::Insert(a, b) => edges.push((a.min(b), a.max(b))),
+                        Update::Delete(a, b) => {
+                            let i = edges.iter().position(|e| *e == (a.min(b), a.max(b))).unwrap();
+                            edges.swap_remove(i);
+                        }
+                    }
+                }
+
+                let mut uf = RollbackUnionFind::new(n);
+                for (a, b) in edges {
+                    uf.union(a, b);
+                }
+                uf.connected(u, v)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(connected_at(n, &updates, &queries), expected);
+    }
+}
@@ -22,7 +22,7 @@ impl<T: Debug> Debug for Graph<AdjList<T>> {
             for (j, k) in t.iter().enumerate() {
                 s.push_str(&format!("{:?}", *k));
                 if j != t.len() - 1 {
-                    s.push_str(&format!(" -> "));
+                    s.push_str(" -> ");
                 }
             }
             s.push('\n');
@@ -35,40 +35,28 @@ impl<T: Debug> Debug for Graph<AdjList<T>> {
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Edge<T>(pub T, pub usize);
 
-impl PartialOrd for Edge<u32> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.0.partial_cmp(&other.0) {
-            Some(core::cmp::Ordering::Equal) => {}
-            Some(Ordering::Greater) => return Some(Ordering::Less),
-            Some(Ordering::Less) => return Some(Ordering::Greater),
-            None => {}
-        }
-        self.1.partial_cmp(&other.1)
+/// Edges are ordered by weight in reverse so that a max heap such as
+/// `BinaryHeap` pops the cheapest edge first, ties are broken by vertex
+impl<T: Ord> PartialOrd for Edge<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for Edge<u32> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.cmp(&other.0).reverse()
+impl<T: Ord> Ord for Edge<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0).reverse().then(self.1.cmp(&other.1))
     }
 }
 
-impl PartialOrd for Edge<u64> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.0.partial_cmp(&other.0) {
-            Some(core::cmp::Ordering::Equal) => {}
-            Some(Ordering::Greater) => return Some(Ordering::Less),
-            Some(Ordering::Less) => return Some(Ordering::Greater),
-            None => {}
-        }
-        self.1.partial_cmp(&other.1)
-    }
-}
+/// Common read-only view over both graph representations so that an
+/// algorithm can be written once for adjacency lists and matrices
+pub trait WeightedGraph<W> {
+    /// Number of vertices in the graph
+    fn vertex_count(&self) -> usize;
 
-impl Ord for Edge<u64> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.0.cmp(&other.0).reverse()
-    }
+    /// Outgoing edges of `vertex` as `Edge(weight, to)`
+    fn edges(&self, vertex: usize) -> impl Iterator<Item = Edge<W>> + '_;
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -95,6 +83,10 @@ where
     pub fn len(&self) -> usize {
         self.internal_repr.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.internal_repr.0.is_empty()
+    }
 }
 
 impl<T> Graph<AdjList<T>> {
@@ -110,6 +102,38 @@ impl<T> Graph<AdjList<T>> {
     pub fn len(&self) -> usize {
         self.internal_repr.0.len()
     }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.internal_repr.0.is_empty()
+    }
+}
+
+impl<W> WeightedGraph<W> for Graph<AdjMatrix<W>>
+where
+    W: Copy + Zero + PartialEq,
+{
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn edges(&self, vertex: usize) -> impl Iterator<Item = Edge<W>> + '_ {
+        self.internal_repr.0[vertex]
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| **x != W::zero())
+            .map(|(i, x)| Edge(*x, i))
+    }
+}
+
+impl<W: Copy> WeightedGraph<W> for Graph<AdjList<Edge<W>>> {
+    fn vertex_count(&self) -> usize {
+        self.len()
+    }
+
+    fn edges(&self, vertex: usize) -> impl Iterator<Item = Edge<W>> + '_ {
+        self.neighbours(vertex).iter().copied()
+    }
 }
 
 impl<T> From<Vec<Vec<T>>> for Graph<AdjList<T>> {
@@ -133,7 +157,7 @@ where
 
 impl<T> From<Vec<Vec<T>>> for Graph<AdjMatrix<T>> {
     fn from(data: Vec<Vec<T>>) -> Self {
-        if data.len() == 0 {
+        if data.is_empty() {
             return Self {
                 internal_repr: AdjMatrix(data),
             };
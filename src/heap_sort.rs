@@ -1,32 +1,71 @@
+use std::cmp::Ordering;
+
 pub struct HeapSort;
 
 impl HeapSort {
     pub fn sort<T: Ord>(buf: &mut [T]) {
+        Self::sort_by(buf, T::cmp)
+    }
+
+    pub fn sort_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
         if buf.len() <= 1 {
             return;
         }
         // we build the heap first
-        Self::build_max_heap(buf);
+        Self::build_max_heap_by(buf, &mut compare);
 
         // after building max heap
         // we can swap the largest to the last index
         for last in (1..buf.len()).rev() {
             buf.swap(0, last);
             // we reduce the size of the heap
-            Self::heapify(&mut buf[..last], 0);
+            Self::heapify_by(&mut buf[..last], 0, &mut compare);
         }
     }
 
+    pub fn sort_by_key<T, K, F>(buf: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::sort_by(buf, |a, b| key(a).cmp(&key(b)))
+    }
+
     /// Convert `buf` into a max heap.
     pub fn build_max_heap<T: Ord>(buf: &mut [T]) {
+        Self::build_max_heap_by(buf, &mut T::cmp)
+    }
+
+    /// Convert `buf` into a heap with the largest element by `compare` on
+    /// top
+    pub fn build_max_heap_by<T, F>(buf: &mut [T], compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // nothing to fix with less than 2 elements, also keeps the
+        // subtraction below from underflowing
+        if buf.len() < 2 {
+            return;
+        }
+
         let last_parent = (buf.len() - 2) / 2;
         for i in (0..=last_parent).rev() {
-            Self::heapify(buf, i);
+            Self::heapify_by(buf, i, compare);
         }
     }
 
     /// Fixes 1 violation at a time then recursively fix affected subtree
     pub fn heapify<T: Ord>(buf: &mut [T], root_idx: usize) {
+        Self::heapify_by(buf, root_idx, &mut T::cmp)
+    }
+
+    pub fn heapify_by<T, F>(buf: &mut [T], root_idx: usize, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
         let (l, r) = Self::child_idx(root_idx);
         let (valid_l, valid_r) = (l < buf.len(), r < buf.len());
 
@@ -35,17 +74,18 @@ impl HeapSort {
             return;
         }
 
-        let (larger_child, large_child_idx) = if valid_l && valid_r && buf[l] < buf[r] {
-            (&buf[r], r)
-        } else {
-            (&buf[l], l)
-        };
+        let (larger_child, large_child_idx) =
+            if valid_l && valid_r && compare(&buf[l], &buf[r]) == Ordering::Less {
+                (&buf[r], r)
+            } else {
+                (&buf[l], l)
+            };
 
-        if *larger_child >= buf[root_idx] {
+        if compare(larger_child, &buf[root_idx]) != Ordering::Less {
             buf.swap(root_idx, large_child_idx);
 
             // recursively fix the affected subtree
-            Self::heapify(buf, large_child_idx)
+            Self::heapify_by(buf, large_child_idx, compare)
         }
     }
 
@@ -79,4 +119,29 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_build_max_heap_small() {
+        let mut empty: [u32; 0] = [];
+        HeapSort::build_max_heap(&mut empty);
+
+        let mut one = [7];
+        HeapSort::build_max_heap(&mut one);
+        assert_eq!(one, [7]);
+
+        let mut two = [1, 2];
+        HeapSort::build_max_heap(&mut two);
+        assert_eq!(two, [2, 1]);
+    }
+
+    #[test]
+    fn test_heap_sort_by() {
+        let mut data = vec![1, 7, 3, 2, 9, 27];
+        HeapSort::sort_by(&mut data, |a, b| b.cmp(a));
+        assert_eq!(data, vec![27, 9, 7, 3, 2, 1]);
+
+        let mut data = vec!["pear", "fig", "banana", "kiwi"];
+        HeapSort::sort_by_key(&mut data, |s| s.len());
+        assert_eq!(data.iter().map(|s| s.len()).collect::<Vec<_>>(), vec![3, 4, 4, 6]);
+    }
 }
@@ -0,0 +1,231 @@
+use crate::priority_queue::IndexedPriorityQueue;
+
+/// Binary min heap over vertex ids `0..n` where every vertex is in the heap
+/// at most once, so its priority can be lowered in place with
+/// [`decrease_key`](IndexedMinHeap::decrease_key) instead of pushing a
+/// duplicate entry.
+///
+/// # Time Complexity
+/// - push, decrease_key, pop_min: O(lgn)
+/// - contains, key: O(1)
+///
+/// # Space Complexity
+/// - O(n) for the position table regardless of how many vertices are queued
+#[derive(Debug, Clone)]
+pub struct IndexedMinHeap<K> {
+    /// (key, vertex) pairs arranged as a binary heap
+    heap: Vec<(K, usize)>,
+    /// position of each vertex in `heap`, `None` if it is not queued
+    pos: Vec<Option<usize>>,
+}
+
+impl<K: Copy + Ord> IndexedMinHeap<K> {
+    /// Empty heap that can hold the vertices `0..n`
+    pub fn new(n: usize) -> Self {
+        Self {
+            heap: Vec::new(),
+            pos: vec![None; n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, vertex: usize) -> bool {
+        self.pos[vertex].is_some()
+    }
+
+    /// Current key of `vertex` if it is queued
+    pub fn key(&self, vertex: usize) -> Option<K> {
+        self.pos[vertex].map(|i| self.heap[i].0)
+    }
+
+    /// Queues `vertex` with `key`.
+    ///
+    /// # Panics
+    /// If `vertex` is already in the heap, use `decrease_key` instead
+    pub fn push(&mut self, vertex: usize, key: K) {
+        assert!(!self.contains(vertex), "vertex {vertex} is already queued");
+        self.heap.push((key, vertex));
+        self.pos[vertex] = Some(self.heap.len() - 1);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    /// Lowers the key of a queued `vertex` to `key`.
+    ///
+    /// # Panics
+    /// If `vertex` is not queued or `key` is larger than its current key
+    pub fn decrease_key(&mut self, vertex: usize, key: K) {
+        let i = self.pos[vertex].expect("vertex is not queued");
+        assert!(key <= self.heap[i].0, "new key is larger than current key");
+        self.heap[i].0 = key;
+        self.sift_up(i);
+    }
+
+    /// Removes the vertex with the smallest key, returns `(vertex, key)`
+    pub fn pop_min(&mut self) -> Option<(usize, K)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        // move the last leaf to the root and let it sink
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, vertex) = self.heap.pop()?;
+        self.pos[vertex] = None;
+        self.sift_down(0);
+
+        Some((vertex, key))
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.pos[self.heap[i].1] = Some(i);
+        self.pos[self.heap[j].1] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i] >= self.heap[parent] {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (l, r) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+
+            if l < self.heap.len() && self.heap[l] < self.heap[smallest] {
+                smallest = l;
+            }
+
+            if r < self.heap.len() && self.heap[r] < self.heap[smallest] {
+                smallest = r;
+            }
+
+            if smallest == i {
+                break;
+            }
+
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<K: Copy + Ord> IndexedPriorityQueue<K> for IndexedMinHeap<K> {
+    fn with_vertices(n: usize) -> Self {
+        Self::new(n)
+    }
+
+    fn push(&mut self, vertex: usize, key: K) {
+        IndexedMinHeap::push(self, vertex, key)
+    }
+
+    fn decrease_key(&mut self, vertex: usize, key: K) {
+        IndexedMinHeap::decrease_key(self, vertex, key)
+    }
+
+    fn contains(&self, vertex: usize) -> bool {
+        IndexedMinHeap::contains(self, vertex)
+    }
+
+    fn pop_min(&mut self) -> Option<(usize, K)> {
+        IndexedMinHeap::pop_min(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        IndexedMinHeap::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nanorand::{Rng, WyRand};
+
+    use super::IndexedMinHeap;
+    use crate::test_utils::check_indexed_pq;
+
+    #[test]
+    fn pop_in_key_order() {
+        let mut heap = IndexedMinHeap::new(5);
+        for (v, k) in [(0, 9), (1, 4), (2, 7), (3, 1), (4, 5)] {
+            heap.push(v, k);
+        }
+
+        heap.decrease_key(2, 0);
+        assert!(heap.contains(2));
+        assert_eq!(heap.key(2), Some(0));
+
+        let mut popped = vec![];
+        while let Some(x) = heap.pop_min() {
+            popped.push(x);
+        }
+
+        assert_eq!(popped, vec![(2, 0), (3, 1), (1, 4), (4, 5), (0, 9)]);
+        assert!(!heap.contains(2));
+    }
+
+    #[test]
+    fn random_decrease_key() {
+        let n = 1000;
+        let mut rng = WyRand::new_seed(42069);
+        let mut heap = IndexedMinHeap::new(n);
+        let mut keys = vec![None; n];
+
+        for _ in 0..10 * n {
+            let v = rng.generate_range(0..n);
+            let k = rng.generate_range(0..100_000u32);
+            match keys[v] {
+                None => {
+                    heap.push(v, k);
+                    keys[v] = Some(k);
+                }
+                Some(old) if k < old => {
+                    heap.decrease_key(v, k);
+                    keys[v] = Some(k);
+                }
+                _ => {}
+            }
+        }
+
+        let mut expected = keys
+            .iter()
+            .enumerate()
+            .filter_map(|(v, k)| k.map(|k| (k, v)))
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+
+        let mut popped = vec![];
+        while let Some((v, k)) = heap.pop_min() {
+            popped.push((k, v));
+        }
+
+        assert_eq!(expected, popped);
+    }
+
+    #[test]
+    fn random_ops() {
+        for seed in 0..20 {
+            check_indexed_pq(IndexedMinHeap::new(500), 500, seed);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_twice() {
+        let mut heap = IndexedMinHeap::new(2);
+        heap.push(0, 1);
+        heap.push(0, 2);
+    }
+}
@@ -1,15 +1,25 @@
+use std::cmp::Ordering;
+
 use crate::{insertion_sort::InsertionSort, merge_sort::MergeSort};
 
-/// # Type parameter
-///
-/// * `S` - Threshold to swap to insertion sort
-pub struct InsertionMergeSort;
+/// Merge sort that switches to insertion sort for slices of at most `s`
+/// elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertionMergeSort {
+    /// Threshold to swap to insertion sort
+    pub s: usize,
+}
 
 impl InsertionMergeSort {
-    pub fn sort_key_cmp<T: Ord + Copy>(buf: &mut [T], s: usize, key_cmp: &mut u64) {
+    pub fn new(s: usize) -> Self {
+        Self { s }
+    }
+
+    pub fn sort_key_cmp<T: Ord>(buf: &mut [T], s: usize, key_cmp: &mut u64) {
         let sz = buf.len();
 
-        if sz <= s {
+        // a threshold of 0 still has to stop at single elements
+        if sz <= s.max(1) {
             InsertionSort::sort_key_cmp(buf, key_cmp);
             return;
         }
@@ -21,19 +31,42 @@ impl InsertionMergeSort {
         MergeSort::merge_key_cmp(l_buf, r_buf, key_cmp);
     }
 
-    pub fn sort<T: Ord + Copy>(buf: &mut [T], s: usize) {
+    pub fn sort<T: Ord>(buf: &mut [T], s: usize) {
+        Self::sort_by(buf, s, T::cmp)
+    }
+
+    pub fn sort_by<T, F>(buf: &mut [T], s: usize, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_by_rec(buf, s, &mut compare)
+    }
+
+    pub fn sort_by_key<T, K, F>(buf: &mut [T], s: usize, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::sort_by(buf, s, |a, b| key(a).cmp(&key(b)))
+    }
+
+    fn sort_by_rec<T, F>(buf: &mut [T], s: usize, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
         let sz = buf.len();
 
-        if sz <= s {
-            InsertionSort::sort(buf);
+        // a threshold of 0 still has to stop at single elements
+        if sz <= s.max(1) {
+            InsertionSort::sort_by(buf, &mut *compare);
             return;
         }
 
         let (l_buf, r_buf) = buf.split_at_mut(buf.len() / 2);
 
-        Self::sort(l_buf, s);
-        Self::sort(r_buf, s);
-        MergeSort::merge(l_buf, r_buf);
+        Self::sort_by_rec(l_buf, s, compare);
+        Self::sort_by_rec(r_buf, s, compare);
+        MergeSort::merge_by(l_buf, r_buf, compare);
     }
 }
 
@@ -51,6 +84,21 @@ mod test {
         assert_sorted(&data);
     }
 
+    #[test]
+    fn test_insertion_merge_sort_by() {
+        let data = gen_random_array::<1000, _>(42069);
+        let mut expected = data.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        let mut descending = data.clone();
+        InsertionMergeSort::sort_by(&mut descending, 15, |a, b| b.cmp(a));
+        assert_eq!(descending, expected);
+
+        let mut by_key = data;
+        InsertionMergeSort::sort_by_key(&mut by_key, 15, |x| u32::MAX - x);
+        assert_eq!(by_key, expected);
+    }
+
     #[cfg(feature = "key_cmp")]
     #[test]
     fn key_cmp_vs_s() {
@@ -111,6 +159,6 @@ mod test {
             ));
         }
 
-        f.write(string.as_bytes()).unwrap();
+        f.write_all(string.as_bytes()).unwrap();
     }
 }
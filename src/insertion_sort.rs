@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 /// # Time Complexity
 /// - Best Case: O(n)
 /// - Worst Case: O(n^2)
@@ -8,13 +10,21 @@ pub struct InsertionSort;
 
 impl InsertionSort {
     pub fn sort<T: Ord>(buf: &mut [T]) {
+        Self::sort_by(buf, T::cmp)
+    }
+
+    /// Sorts with `compare`, equal elements keep their order
+    pub fn sort_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
         // we go through every element except 1 because
         // the first element is assumed to be sorted
         for i in 1..buf.len() {
             // for 1 subsequent element, we keep swapping when curr is smaller than previous element
             // keep swapping until we reach an element that is already smaller
             for j in (1..=i).rev() {
-                if buf[j] < buf[j - 1] {
+                if compare(&buf[j], &buf[j - 1]) == Ordering::Less {
                     // swap
                     buf.swap(j, j - 1)
                 } else {
@@ -24,6 +34,14 @@ impl InsertionSort {
         }
     }
 
+    pub fn sort_by_key<T, K, F>(buf: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::sort_by(buf, |a, b| key(a).cmp(&key(b)))
+    }
+
     pub fn sort_key_cmp<T: Ord>(buf: &mut [T], key_cmp: &mut u64) {
         // we go through every element except 1 because
         // the first element is assumed to be sorted
@@ -56,4 +74,14 @@ mod test {
         InsertionSort::sort(&mut data);
         assert_sorted(&data);
     }
+
+    #[test]
+    fn test_insertion_sort_by() {
+        let mut data = vec![(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')];
+        InsertionSort::sort_by_key(&mut data, |(k, _)| *k);
+        assert_eq!(data, vec![(1, 'b'), (1, 'e'), (2, 'd'), (3, 'a'), (3, 'c')]);
+
+        InsertionSort::sort_by(&mut data, |a, b| b.1.cmp(&a.1));
+        assert_eq!(data, vec![(1, 'e'), (2, 'd'), (3, 'c'), (1, 'b'), (3, 'a')]);
+    }
 }
@@ -1,20 +1,63 @@
+use num_traits::Zero;
+
 use crate::{
     graph::{AdjList, Edge, Graph},
     union_find::{UnionFind, WeightedQuickUnion},
 };
 
-type ListGraph = Graph<AdjList<Edge<u32>>>;
-type KruskalRunInfo = (UnionFind<WeightedQuickUnion, usize, Vec<usize>>, u32);
+type ListGraph<W = u32> = Graph<AdjList<Edge<W>>>;
+
+/// Minimum spanning tree or forest found by Kruskal's
+#[derive(Debug)]
+pub struct KruskalRunInfo<W> {
+    /// chosen edges as `(weight, from, to)` in the order they were added,
+    /// which is by increasing weight
+    pub edges: Vec<(W, usize, usize)>,
+    pub cost: W,
+    /// vertices in the same tree are connected, `uf.count` is the number of
+    /// trees in the forest
+    pub uf: UnionFind<WeightedQuickUnion, usize, Vec<usize>>,
+}
+
+impl<W> KruskalRunInfo<W> {
+    pub fn new(
+        edges: Vec<(W, usize, usize)>,
+        cost: W,
+        uf: UnionFind<WeightedQuickUnion, usize, Vec<usize>>,
+    ) -> Self {
+        Self { edges, cost, uf }
+    }
+}
 
-pub fn kruskal(graph: ListGraph) -> Option<KruskalRunInfo> {
-    // use DSU with Weighted QuickUnion 
+/// Minimum spanning tree, `None` if the graph is not connected
+pub fn kruskal<W>(graph: ListGraph<W>) -> Option<KruskalRunInfo<W>>
+where
+    W: Copy + Ord + Zero,
+{
+    let forest = kruskal_forest(graph);
+
+    // means there is no MST, ie. there exist one vertex that is not connected
+    if forest.uf.count > 1 {
+        return None;
+    }
+
+    Some(forest)
+}
+
+/// Minimum spanning forest, one tree per connected component of the graph
+pub fn kruskal_forest<W>(graph: ListGraph<W>) -> KruskalRunInfo<W>
+where
+    W: Copy + Ord + Zero,
+{
+    // use DSU with Weighted QuickUnion
     let mut uf = UnionFind::<WeightedQuickUnion, usize, Vec<usize>>::new(graph.len());
     // store triplet (weight, from_vertex, to_vertex)
     let mut edges = vec![];
-    // no of edges so far, there will be a total |V| - 1 edges for MST
-    let mut no_edges = 0;
+    // a forest with c trees has |V| - c edges, the loop below either
+    // finishes a spanning tree or uses up all the edges
+    let mut chosen = vec![];
     // min cost
-    let mut cost = 0;
+    let mut cost = W::zero();
 
     // convert info of graph to triplets and store in edges array
     for from in 0..graph.len() {
@@ -26,37 +69,31 @@ pub fn kruskal(graph: ListGraph) -> Option<KruskalRunInfo> {
     // we sort the by weights which is the first element in teh triplet
     // nlgn
     edges.sort_unstable();
-    
+
     // since we alr have the sorted edges by weight
     // we just connect them tgt using DSU
     for (weight, from, to) in edges {
         // we have already found the MST
-        if no_edges == graph.len() - 1 {
+        if uf.count == 1 {
             break;
         }
 
         // if connected => there is a cycle => we dont care and go next
         if !uf.connected(to, from) {
-            println!("Unionizing : {} and {} with weight {}", to, from, weight);
             uf.union(to, from);
-            cost += weight;
-            no_edges += 1;
+            cost = cost + weight;
+            chosen.push((weight, from, to));
         }
     }
 
-    // means there is no MST, ie. there exist one vertex that is not connected
-    if no_edges != graph.len() - 1 {
-        return None;
-    }
-
-    Some((uf, cost))
+    KruskalRunInfo::new(chosen, cost, uf)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         graph::Edge,
-        kruskal::{kruskal, ListGraph},
+        kruskal::{kruskal, kruskal_forest, ListGraph},
     };
 
     #[test]
@@ -74,8 +111,10 @@ mod tests {
 
         let graph = ListGraph::from(adj_list);
         println!("{:?}", &graph);
-        let (uf, cost) = kruskal(graph).unwrap();
-        assert_eq!(21, cost);
+        let mst = kruskal(graph).unwrap();
+        assert_eq!(21, mst.cost);
+        assert_eq!(mst.edges.len(), 7);
+        assert_eq!(mst.edges.iter().map(|(w, _, _)| w).sum::<u32>(), mst.cost);
     }
 
     #[test]
@@ -90,14 +129,15 @@ mod tests {
 
         let graph = ListGraph::from(adj_list);
         println!("{:?}", &graph);
-        let (uf, cost) = kruskal(graph).unwrap();
-        
-        assert_eq!(11, cost);
+        let mst = kruskal(graph).unwrap();
+
+        assert_eq!(11, mst.cost);
+        assert_eq!(mst.edges, vec![(1, 0, 1), (2, 3, 4), (3, 1, 4), (5, 1, 2)]);
     }
 
     #[test]
     fn test_kruskal_graph3() {
-        let adj_list = [
+        let adj_list: [Vec<Edge<u32>>; 8] = [
             vec![],
             vec![],
             vec![],
@@ -109,8 +149,32 @@ mod tests {
         ];
         let graph = ListGraph::from(adj_list);
         println!("{:?}", &graph);
-        if let Some(_) = kruskal(graph){
-            panic!("there shouldnt have any MST");
-        }
+        assert!(kruskal(graph).is_none(), "there shouldnt have any MST");
+    }
+
+    #[test]
+    fn test_kruskal_forest() {
+        // {0, 1, 2} and {3, 4} with 5 on its own
+        let adj_list = [
+            vec![Edge(4, 1), Edge(1, 2)],
+            vec![Edge(4, 0), Edge(2, 2)],
+            vec![Edge(1, 0), Edge(2, 1)],
+            vec![Edge(-3, 4)],
+            vec![Edge(-3, 3)],
+            vec![],
+        ];
+
+        let graph = ListGraph::<i64>::from(adj_list);
+        assert!(kruskal(graph.clone()).is_none());
+
+        let mut forest = kruskal_forest(graph);
+        assert_eq!(forest.cost, 0);
+        assert_eq!(forest.uf.count, 3);
+        assert_eq!(forest.edges, vec![(-3, 3, 4), (1, 0, 2), (2, 1, 2)]);
+        assert!(forest.uf.connected(0, 1));
+        assert!(!forest.uf.connected(2, 3));
+
+        forest.uf.union(0, 5);
+        assert_eq!(forest.uf.count, 2);
     }
 }
@@ -1,17 +1,28 @@
 #![allow(dead_code)]
+#![forbid(unsafe_code)]
 
+pub mod all_pairs;
+pub mod arborescence;
+pub mod bellman_ford;
+pub mod boruvka;
 pub mod djikstra;
+pub mod dynamic_connectivity;
 pub mod graph;
 pub mod heap_sort;
+pub mod indexed_heap;
 pub mod insertion_merge;
 pub mod insertion_sort;
 pub mod merge_sort;
 pub mod min_max_search;
+pub mod mst_verify;
 pub mod quicksort;
+pub mod sorter;
 pub mod union_find;
 pub mod lcs;
 pub mod kruskal;
+pub mod predecessor_tree;
 pub mod prims;
+pub mod priority_queue;
 
 
 use serde::{Deserialize, Serialize};
@@ -39,8 +50,18 @@ pub struct ConfidenceInterval {
 }
 
 pub mod test_utils {
+    use std::{
+        cmp::Reverse,
+        collections::{BinaryHeap, HashSet},
+    };
+
     use nanorand::{Rng, WyRand};
 
+    use crate::{
+        graph::{AdjList, Edge, Graph},
+        priority_queue::IndexedPriorityQueue,
+    };
+
     pub fn gen_random_array<const N: usize, T>(seed: T) -> Vec<u32>
     where
         T: Into<Option<u64>>,
@@ -59,4 +80,94 @@ pub mod test_utils {
             assert!(data[i - 1] <= data[i])
         }
     }
+
+    /// Connected undirected graph with `v` vertices and `e` edges, every edge
+    /// is stored in both directions. A random spanning path is laid down
+    /// first so `e` must be at least `v - 1`.
+    pub fn gen_connected_graph(seed: u64, v: usize, e: usize) -> Graph<AdjList<Edge<u32>>> {
+        assert!(v == 0 || (v - 1 <= e && e <= v * (v - 1) / 2));
+        let mut rng = WyRand::new_seed(seed);
+        // pairs are stored smaller vertex first, a set rather than a matrix
+        // so that large sparse graphs stay cheap to generate
+        let mut adjacent = HashSet::new();
+        let mut pairs = vec![];
+
+        let mut order = (0..v).collect::<Vec<_>>();
+        rng.shuffle(&mut order);
+        for w in order.windows(2) {
+            adjacent.insert((w[0].min(w[1]), w[0].max(w[1])));
+            pairs.push((w[0], w[1]));
+        }
+
+        while pairs.len() < e {
+            let (i, j) = (rng.generate_range(0..v), rng.generate_range(0..v));
+            if i != j && adjacent.insert((i.min(j), i.max(j))) {
+                pairs.push((i, j));
+            }
+        }
+
+        let mut list = vec![vec![]; v];
+        for (i, j) in pairs {
+            let w = rng.generate_range(1..1000u32);
+            list[i].push(Edge(w, j));
+            list[j].push(Edge(w, i));
+        }
+
+        Graph::from(list)
+    }
+
+    /// Runs a random sequence of push, decrease_key and pop_min on `pq` and
+    /// checks every pop against a `BinaryHeap` with lazy deletion. Keys are
+    /// `(key, vertex)` so that ties are popped in the same order by both.
+    pub fn check_indexed_pq<Q>(mut pq: Q, n: usize, seed: u64)
+    where
+        Q: IndexedPriorityQueue<(u32, usize)>,
+    {
+        let mut rng = WyRand::new_seed(seed);
+        let mut model = BinaryHeap::new();
+        let mut keys = vec![None; n];
+
+        type Key = (u32, usize);
+
+        fn model_pop(
+            model: &mut BinaryHeap<Reverse<Key>>,
+            keys: &mut [Option<Key>],
+        ) -> Option<(usize, Key)> {
+            // skip entries that were decreased or already popped
+            while let Some(Reverse(k @ (_, v))) = model.pop() {
+                if keys[v] == Some(k) {
+                    keys[v] = None;
+                    return Some((v, k));
+                }
+            }
+            None
+        }
+
+        for _ in 0..10 * n {
+            let v = rng.generate_range(0..n);
+            match (rng.generate_range(0..3u8), keys[v]) {
+                (0 | 1, None) => {
+                    let k = (rng.generate_range(0..1000u32), v);
+                    pq.push(v, k);
+                    keys[v] = Some(k);
+                    model.push(Reverse(k));
+                }
+                (0 | 1, Some((old, _))) if old > 0 => {
+                    let k = (rng.generate_range(0..old), v);
+                    pq.decrease_key(v, k);
+                    keys[v] = Some(k);
+                    model.push(Reverse(k));
+                }
+                (2, _) => assert_eq!(pq.pop_min(), model_pop(&mut model, &mut keys)),
+                _ => {}
+            }
+            assert_eq!(pq.contains(v), keys[v].is_some());
+        }
+
+        while let Some(x) = model_pop(&mut model, &mut keys) {
+            assert_eq!(pq.pop_min(), Some(x));
+        }
+        assert!(pq.is_empty());
+        assert_eq!(pq.pop_min(), None);
+    }
 }
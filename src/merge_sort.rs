@@ -1,171 +1,278 @@
+use std::{cmp::Ordering, mem};
+
 /// # Time Complexity
 /// - Best Case: O(nlgn)
 /// - Worst Case: O(nlgn)
 ///
 /// # Space Complexity
-/// - O(n) since we used an auxiliary vector to store the data
+/// - O(n) since we used an auxiliary vector to store the merge order
+///
+/// `sort`, `sort_prealloc` and `sort_bottom_up` only ever swap elements
+/// along the merge order, so any `T` can be sorted. `sort_copying` and
+/// `merge_prealloc` clone through a `T` buffer and need `T: Clone`.
+/// Ties are taken from the left half so every variant is stable.
 pub struct MergeSort;
 
 impl MergeSort {
-    pub fn merge_key_cmp<T: Copy + Ord>(l: &mut [T], r: &mut [T], key_cmp: &mut u64) {
-        let (sz, mut l_i, mut r_i) = (l.len() + r.len(), 0, 0);
+    pub fn merge_key_cmp<T: Ord>(l: &mut [T], r: &mut [T], key_cmp: &mut u64) {
+        Self::merge_by(l, r, &mut |a: &T, b: &T| {
+            *key_cmp += 1;
+            a.cmp(b)
+        })
+    }
 
-        let mut aux_buf = Vec::with_capacity(sz);
+    pub fn merge<T: Ord>(l: &mut [T], r: &mut [T]) {
+        Self::merge_by(l, r, &mut T::cmp)
+    }
 
-        // loop until the shorter array ends
-        // we simply push the element by comparing the first of each
-        // we can do this because each l and r are already sorted
-        // from the base case
-        while l_i < l.len() && r_i < r.len() {
-            *key_cmp += 1;
+    /// Merges the sorted halves `l` and `r` with `compare`
+    pub fn merge_by<T, F>(l: &mut [T], r: &mut [T], compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut order = Vec::with_capacity(l.len() + r.len());
+        Self::merge_order(l, r, compare, &mut order);
+        Self::apply_order(l, r, &mut order);
+    }
+
+    /// Merges by cloning into `aux_buf` and back, reusing `aux_buf` instead
+    /// of allocating on every call. This is the original copying merge.
+    pub fn merge_prealloc<T: Ord + Clone>(l: &mut [T], r: &mut [T], aux_buf: &mut Vec<T>) {
+        Self::merge_prealloc_by(l, r, &mut T::cmp, aux_buf)
+    }
 
-            if l[l_i] < r[r_i] {
-                aux_buf.push(l[l_i]);
+    pub fn merge_prealloc_by<T, F>(l: &mut [T], r: &mut [T], compare: &mut F, aux_buf: &mut Vec<T>)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        aux_buf.clear();
+
+        let (mut l_i, mut r_i) = (0, 0);
+
+        // same as merge_order but pushing the elements themselves
+        while l_i < l.len() && r_i < r.len() {
+            if compare(&l[l_i], &r[r_i]) != Ordering::Greater {
+                aux_buf.push(l[l_i].clone());
                 l_i += 1;
             } else {
-                aux_buf.push(r[r_i]);
+                aux_buf.push(r[r_i].clone());
                 r_i += 1;
             }
         }
 
-        // copy remaining
-        for i in l_i..l.len() {
-            aux_buf.push(l[i]);
-        }
-
-        // copy remaining
-        // one of these 2 loops wont run since the condition for the above for
-        // loop exits
-        for i in r_i..r.len() {
-            aux_buf.push(r[i]);
-        }
-
-        // replace original slice with new merged data
-        let mut aux_i = 0;
+        aux_buf.extend_from_slice(&l[l_i..]);
+        aux_buf.extend_from_slice(&r[r_i..]);
 
-        // we set the original buffer to to the new combined aux_buf
-        // we need to track the aux_buf index
-        for i in 0..l.len() {
-            l[i] = aux_buf[aux_i];
-            aux_i += 1;
-        }
+        // replace original slices with the merged data
+        let (aux_l, aux_r) = aux_buf.split_at(l.len());
+        l.clone_from_slice(aux_l);
+        r.clone_from_slice(aux_r);
+    }
 
-        // same here as above for loop
-        for i in 0..r.len() {
-            r[i] = aux_buf[aux_i];
-            aux_i += 1;
-        }
+    /// Same as `merge_by` but reuses `order` for the merge order instead of
+    /// allocating on every call
+    fn merge_order_prealloc_by<T, F>(
+        l: &mut [T],
+        r: &mut [T],
+        compare: &mut F,
+        order: &mut Vec<usize>,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        order.clear();
+        Self::merge_order(l, r, compare, order);
+        Self::apply_order(l, r, order);
     }
-    
-    pub fn merge<T: Copy + Ord>(l: &mut [T], r: &mut [T]) {
-        let (sz, mut l_i, mut r_i) = (l.len() + r.len(), 0, 0);
 
-        let mut aux_buf = Vec::with_capacity(sz);
+    /// Pushes onto `order` where every element of the merged result comes
+    /// from, positions `0..l.len()` are in `l` and the rest are in `r`
+    fn merge_order<T, F>(l: &[T], r: &[T], compare: &mut F, order: &mut Vec<usize>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let (mut l_i, mut r_i) = (0, 0);
 
         // loop until the shorter array ends
-        // we simply push the element by comparing the first of each
+        // we simply take the element by comparing the first of each
         // we can do this because each l and r are already sorted
-        // from the base case
+        // from the base case, ties go to l so the merge is stable
         while l_i < l.len() && r_i < r.len() {
-
-            if l[l_i] < r[r_i] {
-                aux_buf.push(l[l_i]);
+            if compare(&l[l_i], &r[r_i]) != Ordering::Greater {
+                order.push(l_i);
                 l_i += 1;
             } else {
-                aux_buf.push(r[r_i]);
+                order.push(l.len() + r_i);
                 r_i += 1;
             }
         }
 
         // copy remaining
-        for i in l_i..l.len() {
-            aux_buf.push(l[i]);
-        }
+        // one of these 2 extends is a no-op since the condition for the above
+        // while loop exits
+        order.extend(l_i..l.len());
+        order.extend(l.len() + r_i..l.len() + r.len());
+    }
 
-        // copy remaining
-        // one of these 2 loops wont run since the condition for the above for
-        // loop exits
-        for i in r_i..r.len() {
-            aux_buf.push(r[i]);
+    /// Moves the element at `order[k]` to `k` for every `k` by following the
+    /// cycles of the permutation. `order` is used to mark the positions
+    /// already filled so it is left as the identity.
+    fn apply_order<T>(l: &mut [T], r: &mut [T], order: &mut [usize]) {
+        for start in 0..order.len() {
+            // the element taken out of `start` is carried along the cycle,
+            // every swap puts the right element in `curr` and moves the
+            // carried one to where the next element came from
+            let mut curr = start;
+            while order[curr] != start {
+                let src = order[curr];
+                order[curr] = curr;
+                Self::swap_across(l, r, curr, src);
+                curr = src;
+            }
+            order[curr] = curr;
         }
+    }
 
-        // replace original slice with new merged data
-        let mut aux_i = 0;
-
-        // we set the original buffer to to the new combined aux_buf
-        // we need to track the aux_buf index
-        for i in 0..l.len() {
-            l[i] = aux_buf[aux_i];
-            aux_i += 1;
+    /// Swaps two positions of `l` and `r` seen as one slice
+    fn swap_across<T>(l: &mut [T], r: &mut [T], i: usize, j: usize) {
+        let n = l.len();
+        match (i < n, j < n) {
+            (true, true) => l.swap(i, j),
+            (false, false) => r.swap(i - n, j - n),
+            (true, false) => mem::swap(&mut l[i], &mut r[j - n]),
+            (false, true) => mem::swap(&mut l[j], &mut r[i - n]),
         }
+    }
+}
 
-        // same here as above for loop
-        for i in 0..r.len() {
-            r[i] = aux_buf[aux_i];
-            aux_i += 1;
-        }
+impl MergeSort {
+    pub fn sort<T: Ord>(buf: &mut [T]) {
+        Self::sort_by(buf, T::cmp)
     }
 
-    pub fn merge_prealloc<T: Copy + Ord>(l: &mut [T], r: &mut [T], aux_buf: &mut Vec<T>) {
-        aux_buf.clear();
+    pub fn sort_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_by_rec(buf, &mut compare)
+    }
 
-        let (mut l_i, mut r_i) = (0, 0);
+    pub fn sort_by_key<T, K, F>(buf: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::sort_by(buf, |a, b| key(a).cmp(&key(b)))
+    }
 
-        // loop until the shorter array ends
-        // we simply push the element by comparing the first of each
-        // we can do this because each l and r are already sorted
-        // from the base case
-        while l_i < l.len() && r_i < r.len() {
-            if l[l_i] < r[r_i] {
-                aux_buf.push(l[l_i]);
-                l_i += 1;
-            } else {
-                aux_buf.push(r[r_i]);
-                r_i += 1;
-            }
+    /// `compare` is passed down by reference so the recursion does not
+    /// instantiate a new `&mut &mut F` at every level
+    fn sort_by_rec<T, F>(buf: &mut [T], compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // base case, an empty slice would otherwise split forever
+        if buf.len() <= 1 {
+            return;
         }
 
-        // copy remaining
-        // one of these 2 loops wont run since the condition for the above for
-        // loop exits
-        for i in l_i..l.len() {
-            aux_buf.push(l[i]);
-        }
+        let (l_buf, r_buf) = buf.split_at_mut(buf.len() / 2);
 
-        for i in r_i..r.len() {
-            aux_buf.push(r[i]);
-        }
+        Self::sort_by_rec(l_buf, compare);
+        Self::sort_by_rec(r_buf, compare);
+        Self::merge_by(l_buf, r_buf, compare);
+    }
+}
 
-        // replace original slice with new merged data
-        let mut aux_i = 0;
+/// The original merge sort, every merge clones into a freshly allocated
+/// buffer and back. Kept as the baseline the swapping variants are
+/// benchmarked against.
+impl MergeSort {
+    pub fn sort_copying<T: Ord + Clone>(buf: &mut [T]) {
+        Self::sort_copying_by(buf, T::cmp)
+    }
 
-        // we set the original buffer to to the new combined aux_buf
-        // we need to track the aux_buf index
-        for i in 0..l.len() {
-            l[i] = aux_buf[aux_i];
-            aux_i += 1;
-        }
+    pub fn sort_copying_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_copying_rec(buf, &mut compare)
+    }
 
-        // same here as above for loop
-        for i in 0..r.len() {
-            r[i] = aux_buf[aux_i];
-            aux_i += 1;
+    fn sort_copying_rec<T, F>(buf: &mut [T], compare: &mut F)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if buf.len() <= 1 {
+            return;
         }
+
+        let (l_buf, r_buf) = buf.split_at_mut(buf.len() / 2);
+
+        Self::sort_copying_rec(l_buf, compare);
+        Self::sort_copying_rec(r_buf, compare);
+        let mut aux_buf = Vec::with_capacity(l_buf.len() + r_buf.len());
+        Self::merge_prealloc_by(l_buf, r_buf, compare, &mut aux_buf);
     }
 }
 
+/// Variants that allocate the merge buffer once for the whole sort instead
+/// of once per `merge`
 impl MergeSort {
-    pub fn sort<T: Ord + Copy>(buf: &mut [T]) {
-        // base case
-        if buf.len() == 1 {
+    /// Top down merge sort with a single buffer threaded through the
+    /// recursion
+    pub fn sort_prealloc<T: Ord>(buf: &mut [T]) {
+        Self::sort_prealloc_by(buf, T::cmp)
+    }
+
+    pub fn sort_prealloc_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut order = Vec::with_capacity(buf.len());
+        Self::sort_prealloc_rec(buf, &mut compare, &mut order)
+    }
+
+    fn sort_prealloc_rec<T, F>(buf: &mut [T], compare: &mut F, order: &mut Vec<usize>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if buf.len() <= 1 {
             return;
         }
 
         let (l_buf, r_buf) = buf.split_at_mut(buf.len() / 2);
 
-        Self::sort(l_buf);
-        Self::sort(r_buf);
-        Self::merge(l_buf, r_buf);
+        Self::sort_prealloc_rec(l_buf, compare, order);
+        Self::sort_prealloc_rec(r_buf, compare, order);
+        Self::merge_order_prealloc_by(l_buf, r_buf, compare, order);
+    }
+
+    /// Bottom up merge sort, merges runs of 1, 2, 4, ... elements until the
+    /// whole slice is one run. No recursion and a single buffer.
+    pub fn sort_bottom_up<T: Ord>(buf: &mut [T]) {
+        Self::sort_bottom_up_by(buf, T::cmp)
+    }
+
+    pub fn sort_bottom_up_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut order = Vec::with_capacity(buf.len());
+        let mut width = 1;
+
+        while width < buf.len() {
+            // the last chunk may be shorter or have no right run at all
+            for chunk in buf.chunks_mut(2 * width) {
+                if chunk.len() > width {
+                    let (l_buf, r_buf) = chunk.split_at_mut(width);
+                    Self::merge_order_prealloc_by(l_buf, r_buf, &mut compare, &mut order);
+                }
+            }
+            width *= 2;
+        }
     }
 }
 
@@ -181,4 +288,81 @@ mod test {
         MergeSort::sort(&mut data);
         assert_sorted(&data);
     }
+
+    #[test]
+    fn test_merge_sort_by() {
+        let mut data = vec![2.5, -1.0, 9.75, 0.0, -3.5];
+        MergeSort::sort_by(&mut data, |a: &f64, b| b.partial_cmp(a).unwrap());
+        assert_eq!(data, vec![9.75, 2.5, 0.0, -1.0, -3.5]);
+
+        let mut data = vec![(3, 'a'), (1, 'b'), (2, 'c')];
+        MergeSort::sort_by_key(&mut data, |(k, _)| *k);
+        assert_eq!(data, vec![(1, 'b'), (2, 'c'), (3, 'a')]);
+    }
+
+    #[test]
+    fn test_merge_prealloc_strings() {
+        let mut l = ["b", "d", "f", "h"].map(String::from);
+        let mut r = ["a", "c", "e"].map(String::from);
+        let mut aux_buf = vec![];
+
+        MergeSort::merge_prealloc(&mut l, &mut r, &mut aux_buf);
+        assert_eq!(l, ["a", "b", "c", "d"]);
+        assert_eq!(r, ["e", "f", "h"]);
+
+        let mut l = ["b", "d"].map(String::from);
+        let mut r = ["a", "c"].map(String::from);
+        let mut order = vec![];
+
+        MergeSort::merge_order_prealloc_by(&mut l, &mut r, &mut String::cmp, &mut order);
+        assert_eq!(l, ["a", "b"]);
+        assert_eq!(r, ["c", "d"]);
+        assert_eq!(order, (0..4).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_merge_sort_variants() {
+        let data = gen_random_array::<10000, _>(42069);
+
+        for sort in [
+            MergeSort::sort,
+            MergeSort::sort_copying,
+            MergeSort::sort_prealloc,
+            MergeSort::sort_bottom_up,
+        ] {
+            for len in [0, 1, 2, 3, 1000, 1023, 1025, 10000] {
+                let mut buf = data[..len].to_vec();
+                let mut expected = data[..len].to_vec();
+                expected.sort();
+                sort(&mut buf);
+                assert_eq!(buf, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_sort_stable() {
+        // few distinct keys so there are many ties, the original index must
+        // stay in increasing order within every key
+        let keys = gen_random_array::<5000, _>(42069);
+        let data = keys.iter().enumerate().map(|(i, k)| (k % 16, i)).collect::<Vec<_>>();
+        let mut expected = data.clone();
+        expected.sort_by_key(|(k, _)| *k);
+
+        let key = |(k, _): &(u32, usize)| *k;
+        let mut top_down = data.clone();
+        MergeSort::sort_by_key(&mut top_down, key);
+        let mut prealloc = data.clone();
+        MergeSort::sort_prealloc_by(&mut prealloc, |a, b| key(a).cmp(&key(b)));
+        let mut copying = data.clone();
+        MergeSort::sort_copying_by(&mut copying, |a, b| key(a).cmp(&key(b)));
+        let mut bottom_up = data;
+        MergeSort::sort_bottom_up_by(&mut bottom_up, |a, b| key(a).cmp(&key(b)));
+
+        assert_eq!(top_down, expected);
+        assert_eq!(prealloc, expected);
+        assert_eq!(copying, expected);
+        assert_eq!(bottom_up, expected);
+    }
 }
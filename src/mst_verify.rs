@@ -0,0 +1,414 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    ops::Sub,
+};
+
+use num_traits::Zero;
+
+use crate::{
+    graph::{AdjList, Edge, Graph},
+    kruskal::kruskal,
+    predecessor_tree::PredecessorTree,
+    union_find::{UnionFind, WeightedQuickUnion},
+};
+
+type ListGraph<W> = Graph<AdjList<Edge<W>>>;
+
+/// `(weight, from, to)` as returned by Kruskal's
+type WeightedEdge<W> = (W, usize, usize);
+
+/// Why a candidate set of `(weight, from, to)` edges is not a minimum
+/// spanning tree of the graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MstError<W> {
+    /// the edge is not in the graph with this weight
+    UnknownEdge(WeightedEdge<W>),
+    /// the edge joins two vertices that are already connected
+    Cycle(WeightedEdge<W>),
+    /// the edges leave the graph in more than one piece
+    NotSpanning { components: usize },
+    /// `edge` is not in the tree but is cheaper than `replaces`, the
+    /// heaviest tree edge on the path between its ends, so swapping them
+    /// gives a cheaper tree
+    CycleProperty {
+        edge: WeightedEdge<W>,
+        replaces: WeightedEdge<W>,
+    },
+}
+
+/// Checks that `edges` is a minimum spanning tree of `graph`.
+///
+/// The graph is read as undirected. A tree is minimum iff every edge outside
+/// of it is at least as heavy as every tree edge on the cycle it would close,
+/// which is checked with [`PathMax`] instead of comparing against a freshly
+/// computed MST.
+///
+/// # Time Complexity
+/// - O((V + E)lgV)
+pub fn verify_mst<W>(graph: &ListGraph<W>, edges: &[WeightedEdge<W>]) -> Result<(), MstError<W>>
+where
+    W: Copy + Ord,
+{
+    let sz_v = graph.len();
+    let mut uf = UnionFind::<WeightedQuickUnion, usize, Vec<usize>>::new(sz_v);
+
+    for &(weight, from, to) in edges {
+        // an end outside the graph cant be looked up at all
+        let exists = from < sz_v
+            && to < sz_v
+            && (graph.neighbours(from).contains(&Edge(weight, to))
+                || graph.neighbours(to).contains(&Edge(weight, from)));
+        if !exists {
+            return Err(MstError::UnknownEdge((weight, from, to)));
+        }
+
+        if uf.connected(from, to) {
+            return Err(MstError::Cycle((weight, from, to)));
+        }
+        uf.union(from, to);
+    }
+
+    if uf.count > 1 {
+        return Err(MstError::NotSpanning {
+            components: uf.count,
+        });
+    }
+
+    let path_max = PathMax::new(sz_v, edges);
+    for from in 0..sz_v {
+        for Edge(weight, to) in graph.neighbours(from) {
+            if let Some((heaviest, child)) = path_max.query(from, *to) {
+                if *weight < heaviest {
+                    return Err(MstError::CycleProperty {
+                        edge: (*weight, from, *to),
+                        replaces: (heaviest, child, path_max.parent(child)),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// [`verify_mst`] for a tree given as a predecessor array such as
+/// [`MstInfo`](crate::prims::MstInfo)
+pub fn verify_tree<W, T>(graph: &ListGraph<W>, tree: &T) -> Result<(), MstError<W>>
+where
+    W: Copy + Ord,
+    T: PredecessorTree<W>,
+{
+    let edges = tree
+        .tree_edges()
+        .map(|(from, Edge(weight, to))| (weight, from, to))
+        .collect::<Vec<_>>();
+
+    verify_mst(graph, &edges)
+}
+
+/// Cheapest spanning tree that differs from the minimum spanning tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondBestMst<W> {
+    pub edges: Vec<WeightedEdge<W>>,
+    pub cost: W,
+    /// the MST edge that was taken out
+    pub removed: WeightedEdge<W>,
+    /// the edge that took its place
+    pub added: WeightedEdge<W>,
+}
+
+/// Second best minimum spanning tree built from Kruskal's output.
+///
+/// The second best tree differs from the MST by exactly one edge, so every
+/// non-tree edge is tried in place of the heaviest tree edge on the cycle it
+/// closes. Its cost may equal the MST cost if there are ties. The graph must
+/// be undirected with both directions stored. Returns `None` if the graph
+/// has no MST or no other spanning tree.
+///
+/// # Time Complexity
+/// - O(ElgV)
+pub fn second_best_mst<W>(graph: ListGraph<W>) -> Option<SecondBestMst<W>>
+where
+    W: Copy + Ord + Zero + Sub<Output = W>,
+{
+    let sz_v = graph.len();
+    let mst = kruskal(graph.clone())?;
+    let path_max = PathMax::new(sz_v, &mst.edges);
+
+    // each tree edge shows up in the graph once we only look at one
+    // direction, skip exactly that many copies of it
+    let mut in_tree = BTreeMap::new();
+    for &(weight, from, to) in &mst.edges {
+        *in_tree.entry((from.min(to), from.max(to), weight)).or_insert(0) += 1;
+    }
+
+    let mut best: Option<(W, WeightedEdge<W>, WeightedEdge<W>)> = None;
+    for from in 0..sz_v {
+        for Edge(weight, to) in graph.neighbours(from) {
+            if from >= *to {
+                continue;
+            }
+
+            if let Some(count) = in_tree.get_mut(&(from, *to, *weight)) {
+                if *count > 0 {
+                    *count -= 1;
+                    continue;
+                }
+            }
+
+            let Some((heaviest, child)) = path_max.query(from, *to) else {
+                continue;
+            };
+
+            let cost = mst.cost - heaviest + *weight;
+            if best.is_none_or(|(c, _, _)| cost < c) {
+                let removed = (heaviest, child, path_max.parent(child));
+                best = Some((cost, removed, (*weight, from, *to)));
+            }
+        }
+    }
+
+    let (cost, removed, added) = best?;
+    let mut edges = mst.edges;
+    let i = edges
+        .iter()
+        .position(|&(w, f, t)| {
+            let (_, a, b) = removed;
+            w == removed.0 && (f.min(t), f.max(t)) == (a.min(b), a.max(b))
+        })
+        .expect("removed edge is in the mst");
+    edges[i] = added;
+
+    Some(SecondBestMst {
+        edges,
+        cost,
+        removed,
+        added,
+    })
+}
+
+/// Heaviest edge on the path between two vertices of a forest, answered in
+/// O(lgV) with binary lifting.
+///
+/// `up[k][v]` is the ancestor `2^k` levels above `v` and `heaviest[k][v]` the
+/// heaviest edge on the way there as `(weight, child)`, where the edge is the
+/// one between `child` and its parent.
+#[derive(Debug, Clone)]
+pub struct PathMax<W> {
+    depth: Vec<usize>,
+    up: Vec<Vec<usize>>,
+    heaviest: Vec<Vec<Option<(W, usize)>>>,
+}
+
+impl<W: Copy + Ord> PathMax<W> {
+    /// `edges` must form a forest over the vertices `0..sz_v`
+    pub fn new(sz_v: usize, edges: &[WeightedEdge<W>]) -> Self {
+        let mut adj = vec![vec![]; sz_v];
+        for &(weight, from, to) in edges {
+            adj[from].push((weight, to));
+            adj[to].push((weight, from));
+        }
+
+        let levels = (usize::BITS - sz_v.leading_zeros()).max(1) as usize;
+        let mut depth = vec![0; sz_v];
+        let mut up = vec![(0..sz_v).collect::<Vec<_>>(); levels];
+        let mut heaviest = vec![vec![None; sz_v]; levels];
+        let mut visited = vec![false; sz_v];
+
+        // bfs from every root so parents are filled in before children
+        for root in 0..sz_v {
+            if visited[root] {
+                continue;
+            }
+
+            visited[root] = true;
+            let mut queue = VecDeque::from([root]);
+            while let Some(v) = queue.pop_front() {
+                for &(weight, child) in &adj[v] {
+                    if visited[child] {
+                        continue;
+                    }
+                    visited[child] = true;
+                    depth[child] = depth[v] + 1;
+                    up[0][child] = v;
+                    heaviest[0][child] = Some((weight, child));
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        for k in 1..levels {
+            for v in 0..sz_v {
+                let mid = up[k - 1][v];
+                up[k][v] = up[k - 1][mid];
+                heaviest[k][v] = heaviest[k - 1][v].max(heaviest[k - 1][mid]);
+            }
+        }
+
+        Self {
+            depth,
+            up,
+            heaviest,
+        }
+    }
+
+    /// Parent of `v`, a root is its own parent
+    pub fn parent(&self, v: usize) -> usize {
+        self.up[0][v]
+    }
+
+    /// Heaviest edge on the path from `u` to `v` as `(weight, child)`.
+    /// `None` if `u == v` or they are in different trees.
+    pub fn query(&self, mut u: usize, mut v: usize) -> Option<(W, usize)> {
+        let mut best = None;
+        if self.depth[u] < self.depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+
+        // lift u to the depth of v
+        let mut diff = self.depth[u] - self.depth[v];
+        let mut k = 0;
+        while diff > 0 {
+            if diff & 1 == 1 {
+                best = best.max(self.heaviest[k][u]);
+                u = self.up[k][u];
+            }
+            diff >>= 1;
+            k += 1;
+        }
+
+        if u == v {
+            return best;
+        }
+
+        for k in (0..self.up.len()).rev() {
+            if self.up[k][u] != self.up[k][v] {
+                best = best.max(self.heaviest[k][u]).max(self.heaviest[k][v]);
+                u = self.up[k][u];
+                v = self.up[k][v];
+            }
+        }
+
+        // different roots means different trees
+        if self.up[0][u] != self.up[0][v] {
+            return None;
+        }
+
+        best.max(self.heaviest[0][u]).max(self.heaviest[0][v])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graph::Edge,
+        kruskal::kruskal,
+        prims::prims,
+        test_utils::gen_connected_graph,
+    };
+
+    use super::{second_best_mst, verify_mst, verify_tree, ListGraph, MstError};
+
+    fn graph() -> ListGraph<u32> {
+        ListGraph::from([
+            vec![Edge(1, 1), Edge(7, 2)],
+            vec![Edge(1, 0), Edge(5, 2), Edge(4, 3), Edge(3, 4)],
+            vec![Edge(7, 0), Edge(5, 1), Edge(6, 4)],
+            vec![Edge(4, 1), Edge(2, 4)],
+            vec![Edge(2, 3), Edge(6, 2), Edge(3, 1)],
+        ])
+    }
+
+    #[test]
+    fn accepts_mst() {
+        let graph = graph();
+        let mst = kruskal(graph.clone()).unwrap();
+        assert_eq!(verify_mst(&graph, &mst.edges), Ok(()));
+        assert_eq!(verify_tree(&graph, &prims(graph.clone(), 0).unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn rejects_bad_trees() {
+        let graph = graph();
+
+        assert_eq!(
+            verify_mst(&graph, &[(9, 0, 4)]),
+            Err(MstError::UnknownEdge((9, 0, 4)))
+        );
+        assert_eq!(
+            verify_mst(&graph, &[(1, 0, 99)]),
+            Err(MstError::UnknownEdge((1, 0, 99)))
+        );
+        assert_eq!(
+            verify_mst(&graph, &[(1, 99, 0)]),
+            Err(MstError::UnknownEdge((1, 99, 0)))
+        );
+        assert_eq!(
+            verify_mst(&graph, &[(2, 3, 4), (3, 1, 4), (4, 1, 3)]),
+            Err(MstError::Cycle((4, 1, 3)))
+        );
+        assert_eq!(
+            verify_mst(&graph, &[(1, 0, 1), (2, 3, 4)]),
+            Err(MstError::NotSpanning { components: 3 })
+        );
+
+        // spanning tree using 0 - 2 with weight 7 instead of 1 - 2
+        let err = verify_mst(&graph, &[(1, 0, 1), (2, 3, 4), (3, 1, 4), (7, 0, 2)]).unwrap_err();
+        match err {
+            MstError::CycleProperty { edge, replaces } => {
+                assert_eq!(replaces.0, 7);
+                assert!(edge.0 < 7);
+            }
+            _ => panic!("expected the cycle property to fail, got {err:?}"),
+        }
+    }
+
+    #[test]
+    fn second_best() {
+        let graph = graph();
+        let second = second_best_mst(graph.clone()).unwrap();
+
+        // swapping 1 - 4 (3) for 1 - 3 (4) costs one more than the mst
+        assert_eq!(second.cost, 12);
+        assert_eq!(second.added, (4, 1, 3));
+        assert_eq!(second.removed.0, 3);
+        assert_eq!(second.edges.len(), 4);
+        assert_eq!(second.edges.iter().map(|(w, _, _)| w).sum::<u32>(), 12);
+        assert!(matches!(
+            verify_mst(&graph, &second.edges),
+            Err(MstError::CycleProperty { .. })
+        ));
+    }
+
+    #[test]
+    fn second_best_random() {
+        for seed in 0..20 {
+            let graph = gen_connected_graph(seed, 60, 200);
+            let mst = kruskal(graph.clone()).unwrap();
+            assert_eq!(verify_mst(&graph, &mst.edges), Ok(()));
+
+            let second = second_best_mst(graph.clone()).unwrap();
+            assert!(second.cost >= mst.cost);
+            assert_eq!(second.edges.iter().map(|(w, _, _)| w).sum::<u32>(), second.cost);
+
+            // brute force, drop each mst edge in turn and rebuild
+            let brute = mst
+                .edges
+                .iter()
+                .filter_map(|&(w, f, t)| {
+                    let mut list = graph.internal_repr.0.clone();
+                    list[f].retain(|e| *e != Edge(w, t));
+                    list[t].retain(|e| *e != Edge(w, f));
+                    kruskal(ListGraph::from(list)).map(|m| m.cost)
+                })
+                .min();
+            assert_eq!(Some(second.cost), brute);
+        }
+    }
+
+    #[test]
+    fn tree_has_no_second_best() {
+        let graph = ListGraph::from([vec![Edge(1, 1)], vec![Edge(1, 0)]]);
+        assert!(second_best_mst(graph).is_none());
+    }
+}
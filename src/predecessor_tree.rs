@@ -0,0 +1,223 @@
+use std::ops::Sub;
+
+use num_traits::Zero;
+
+use crate::{
+    djikstra::DjikstraRunInfo,
+    graph::{AdjList, Edge, Graph},
+    prims::MstInfo,
+};
+
+/// Tree stored as a predecessor array such as a shortest path tree or a
+/// minimum spanning tree.
+///
+/// Only `predecessors`, `contains` and `edge_weight` have to be provided.
+/// The walks are bounded by the number of vertices so a malformed array with
+/// a cycle in it gives `None` instead of looping forever.
+pub trait PredecessorTree<W: Copy> {
+    /// `predecessors[v]` is the parent of `v`, `None` for the root and for
+    /// vertices outside of the tree
+    fn predecessors(&self) -> &[Option<usize>];
+
+    /// Whether `vertex` was reached, i.e. is the root or has a parent
+    fn contains(&self, vertex: usize) -> bool;
+
+    /// Weight of the edge from the parent of `vertex` to `vertex`, only
+    /// meaningful if `vertex` has a parent
+    fn edge_weight(&self, vertex: usize) -> W;
+
+    /// Vertices from the root to `target`, both ends included. Returns `None`
+    /// if `target` is not in the tree or its ancestors form a cycle.
+    fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        let predecessors = self.predecessors();
+        if !self.contains(target) {
+            return None;
+        }
+
+        let mut path = vec![target];
+        let mut curr = target;
+        while let Some(prev) = predecessors[curr] {
+            // a path cant have more vertices than the graph
+            if path.len() == predecessors.len() {
+                return None;
+            }
+            path.push(prev);
+            curr = prev;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every tree edge as `(parent, Edge(weight, child))`
+    fn tree_edges(&self) -> impl Iterator<Item = (usize, Edge<W>)> + '_ {
+        self.predecessors()
+            .iter()
+            .enumerate()
+            .filter_map(|(v, p)| p.map(|p| (p, Edge(self.edge_weight(v), v))))
+    }
+
+    /// Number of edges between the root and each vertex. `None` if the vertex
+    /// is not in the tree or hangs off a cycle.
+    fn depths(&self) -> Vec<Option<usize>> {
+        let predecessors = self.predecessors();
+        let sz_v = predecessors.len();
+        let mut depths = vec![None; sz_v];
+        // vertices already walked that turned out to be unreachable from a
+        // root, so later walks through them can stop early
+        let mut dead = vec![false; sz_v];
+
+        for v in 0..sz_v {
+            // climb until a vertex with a known depth or the root
+            let mut stack = vec![];
+            let mut curr = v;
+            let base = loop {
+                if let Some(d) = depths[curr] {
+                    break Some(d);
+                }
+                if dead[curr] || !self.contains(curr) || stack.len() > sz_v {
+                    break None;
+                }
+                match predecessors[curr] {
+                    Some(p) => {
+                        stack.push(curr);
+                        curr = p;
+                    }
+                    None => {
+                        depths[curr] = Some(0);
+                        break Some(0);
+                    }
+                }
+            };
+
+            match base {
+                Some(d) => {
+                    for (i, x) in stack.into_iter().rev().enumerate() {
+                        depths[x] = Some(d + i + 1);
+                    }
+                }
+                None => stack.into_iter().for_each(|x| dead[x] = true),
+            }
+        }
+
+        depths
+    }
+
+    /// The tree as a directed graph with edges from parent to child
+    fn to_graph(&self) -> Graph<AdjList<Edge<W>>> {
+        let mut list = vec![vec![]; self.predecessors().len()];
+        for (parent, edge) in self.tree_edges() {
+            list[parent].push(edge);
+        }
+
+        Graph::from(list)
+    }
+}
+
+/// Weights are recovered from the distances since the distance to a vertex
+/// is the distance to its parent plus the edge between them
+impl<W> PredecessorTree<W> for DjikstraRunInfo<W>
+where
+    W: Copy + Zero + Sub<Output = W>,
+{
+    fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    fn contains(&self, vertex: usize) -> bool {
+        self.visited.get(vertex).copied().unwrap_or(false)
+    }
+
+    fn edge_weight(&self, vertex: usize) -> W {
+        match self.predecessors[vertex] {
+            Some(p) => self.distance[vertex] - self.distance[p],
+            None => W::zero(),
+        }
+    }
+}
+
+/// `distance[v]` already holds the weight of the edge joining `v` to the tree
+impl<W: Copy> PredecessorTree<W> for MstInfo<W> {
+    fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    fn contains(&self, vertex: usize) -> bool {
+        self.visited.get(vertex).copied().unwrap_or(false)
+    }
+
+    fn edge_weight(&self, vertex: usize) -> W {
+        self.distance[vertex]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        djikstra::{djikstra, DjikstraRunInfo},
+        graph::{AdjList, AdjMatrix, Edge, Graph},
+        prims::prims,
+    };
+
+    use super::PredecessorTree;
+
+    #[test]
+    fn djikstra_tree() {
+        let graph: Graph<AdjMatrix<u32>> = Graph::from(vec![
+            vec![0, 10, 3, 0, 0],
+            vec![0, 0, 1, 2, 0],
+            vec![0, 4, 0, 8, 2],
+            vec![0, 0, 0, 0, 7],
+            vec![0, 0, 0, 9, 0],
+        ]);
+
+        let info = djikstra(&graph, 0);
+        assert_eq!(info.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(info.depths(), vec![Some(0), Some(2), Some(1), Some(3), Some(2)]);
+
+        let mut edges = info.tree_edges().collect::<Vec<_>>();
+        edges.sort_by_key(|(p, Edge(_, v))| (*p, *v));
+        assert_eq!(
+            edges,
+            vec![(0, Edge(3, 2)), (1, Edge(2, 3)), (2, Edge(4, 1)), (2, Edge(2, 4))]
+        );
+
+        // the exported tree has the same shortest paths as the graph
+        let tree = info.to_graph();
+        assert_eq!(djikstra(&tree, 0).distance, info.distance);
+    }
+
+    #[test]
+    fn mst_tree() {
+        let graph = Graph::from(vec![
+            vec![Edge(2, 1), Edge(3, 3)],
+            vec![Edge(2, 0), Edge(3, 2), Edge(1, 3)],
+            vec![Edge(3, 1), Edge(4, 3)],
+            vec![Edge(3, 0), Edge(1, 1), Edge(4, 2)],
+        ]);
+
+        let mst = prims(graph, 0).unwrap();
+        let cost = mst.tree_edges().map(|(_, Edge(w, _))| w).sum::<u32>();
+        assert_eq!(cost, mst.min_cost);
+        assert_eq!(mst.depths(), vec![Some(0), Some(1), Some(2), Some(2)]);
+
+        let tree: Graph<AdjList<Edge<u32>>> = mst.to_graph();
+        assert_eq!(tree.neighbours(1), &[Edge(3, 2), Edge(1, 3)]);
+    }
+
+    #[test]
+    fn malformed_predecessors() {
+        // 1 -> 2 -> 3 -> 1 is a cycle and 4 hangs off it, 5 is unreachable
+        let info = DjikstraRunInfo::new(
+            vec![0, 1, 2, 3, 4, u32::MAX],
+            vec![None, Some(3), Some(1), Some(2), Some(3), None],
+            vec![true, true, true, true, true, false],
+        );
+
+        assert_eq!(info.path_to(0), Some(vec![0]));
+        assert_eq!(info.path_to(2), None);
+        assert_eq!(info.path_to(4), None);
+        assert_eq!(info.path_to(5), None);
+        assert_eq!(info.depths(), vec![Some(0), None, None, None, None, None]);
+    }
+}
@@ -1,7 +1,15 @@
-use crate::graph::{AdjList, Edge, Graph};
+use crate::{
+    graph::{AdjList, Edge, Graph},
+    indexed_heap::IndexedMinHeap,
+    priority_queue::{IndexedPriorityQueue, PriorityQueue},
+};
 use std::{cmp::Reverse, collections::BinaryHeap};
 
 type ListGraph = Graph<AdjList<Edge<u32>>>;
+
+/// Queue entry used by Prim's, `(weight, vertex, predecessor)` wrapped in
+/// `Reverse` so that the cheapest edge is popped first
+pub type PrimsEntry<T> = Reverse<(T, usize, Option<usize>)>;
 #[derive(Debug, Clone)]
 pub struct MstInfo<T> {
     pub predecessors: Vec<Option<usize>>,
@@ -28,13 +36,22 @@ impl<T> MstInfo<T> {
 
 /// Minimum spanning tree : Connect all vertexes without any cycle
 pub fn prims(graph: ListGraph, src: usize) -> Option<MstInfo<u32>> {
+    prims_with::<BinaryHeap<_>>(graph, src)
+}
+
+/// Prim's generic over the priority queue `Q`
+pub fn prims_with<Q>(graph: ListGraph, src: usize) -> Option<MstInfo<u32>>
+where
+    Q: PriorityQueue<PrimsEntry<u32>> + Default,
+{
     // S
     let mut visited = vec![false; graph.len()];
     let mut predecessors = vec![None::<usize>; graph.len()];
     let mut distance = vec![u32::MAX; graph.len()];
 
     // minimising priority queue
-    let mut pq = BinaryHeap::from([Reverse((0, src, None))]);
+    let mut pq = Q::default();
+    pq.push(Reverse((0, src, None)));
     let mut no_visited = 0;
     let mut min_cost = 0;
     distance[src] = 0;
@@ -46,24 +63,77 @@ pub fn prims(graph: ListGraph, src: usize) -> Option<MstInfo<u32>> {
             continue;
         }
 
-        // #[cfg(debug_assertions)]
-        println!("S: {:?}", visited);
-        println!("Predecessor: {:?}", predecessors);
-        println!("Distance: {:?}\n", distance);
+        #[cfg(feature = "trace")]
+        {
+            println!("S: {:?}", visited);
+            println!("Predecessor: {:?}", predecessors);
+            println!("Distance: {:?}\n", distance);
+        }
 
+        // the entry that settles a vertex carries the edge that joins it to
+        // the tree, so only now do we know its predecessor
         visited[vertex] = true;
         min_cost += weight;
         no_visited += 1;
         predecessors[vertex] = predecessor;
 
-        if let Some(_) = predecessor {
+        if predecessor.is_some() {
             distance[vertex] = weight;
         }
 
         for Edge(weight, neighbour) in graph.neighbours(vertex) {
             if !visited[*neighbour] {
                 pq.push(Reverse((*weight, *neighbour, Some(vertex))));
-                predecessors[*neighbour] = Some(vertex);
+            }
+        }
+    }
+
+    if no_visited != graph.len() {
+        return None;
+    }
+
+    Some(MstInfo::new(predecessors, visited, distance, min_cost))
+}
+
+/// Prim's using `IndexedMinHeap` with decrease key
+pub fn prims_eager(graph: ListGraph, src: usize) -> Option<MstInfo<u32>> {
+    prims_eager_with::<IndexedMinHeap<_>>(graph, src)
+}
+
+/// Prim's with the queue keyed by the cheapest known edge into the tree,
+/// generic over the indexed priority queue `Q`. Every vertex is queued at
+/// most once and lowered with `decrease_key`.
+pub fn prims_eager_with<Q>(graph: ListGraph, src: usize) -> Option<MstInfo<u32>>
+where
+    Q: IndexedPriorityQueue<u32>,
+{
+    let mut visited = vec![false; graph.len()];
+    let mut predecessors = vec![None::<usize>; graph.len()];
+    let mut distance = vec![u32::MAX; graph.len()];
+
+    let mut pq = Q::with_vertices(graph.len());
+    let mut no_visited = 0;
+    let mut min_cost = 0;
+    distance[src] = 0;
+    pq.push(src, 0);
+
+    while let Some((vertex, weight)) = pq.pop_min() {
+        visited[vertex] = true;
+        min_cost += weight;
+        no_visited += 1;
+
+        for Edge(weight, neighbour) in graph.neighbours(vertex) {
+            if visited[*neighbour] || distance[*neighbour] <= *weight {
+                continue;
+            }
+
+            distance[*neighbour] = *weight;
+            predecessors[*neighbour] = Some(vertex);
+
+            if pq.contains(*neighbour) {
+                pq.decrease_key(*neighbour, *weight);
+            } else {
+                pq.push(*neighbour, *weight);
             }
         }
     }
@@ -79,7 +149,10 @@ pub fn prims(graph: ListGraph, src: usize) -> Option<MstInfo<u32>> {
 mod tests {
     use crate::{
         graph::Edge,
-        prims::{prims, ListGraph},
+        kruskal::kruskal,
+        predecessor_tree::PredecessorTree,
+        prims::{prims, prims_eager, ListGraph},
+        test_utils::gen_connected_graph,
     };
 
     use super::MstInfo;
@@ -105,9 +178,10 @@ mod tests {
 
         let graph = ListGraph::from(adj_list);
         println!("{:?}", &graph);
-        let mst_info = prims(graph, 0).unwrap();
+        let mst_info = prims(graph.clone(), 0).unwrap();
         println!("{:?}", mst_info);
         assert_eq!(mst_info.min_cost, 21);
+        assert_eq!(prims_eager(graph, 0).unwrap().min_cost, 21);
     }
 
     #[test]
@@ -142,10 +216,7 @@ mod tests {
         let graph = ListGraph::from(adj_list);
         println!("{:?}", &graph);
         let mst_info = prims(graph, 0);
-        match mst_info {
-            Some(_) => panic!("Should be None!"),
-            None => {}
-        };
+        assert!(mst_info.is_none(), "Should be None!");
     }
 
     #[test]
@@ -154,10 +225,54 @@ mod tests {
 
         let graph = ListGraph::from(adj_list);
         println!("{:?}", &graph);
-        let mst_info = prims(graph, 0);
-        match mst_info {
-            Some(_) => panic!("Should be None!"),
-            None => {}
-        };
+        let mst_info = prims(graph.clone(), 0);
+        assert!(mst_info.is_none(), "Should be None!");
+        assert!(prims_eager(graph, 0).is_none(), "Should be None!");
+    }
+
+    /// Tree edges must be real edges of `graph`, reach every vertex from
+    /// `src` and add up to `min_cost`
+    fn assert_spanning_tree(graph: &ListGraph, mst: &MstInfo<u32>, src: usize) {
+        for (from, Edge(weight, to)) in mst.tree_edges() {
+            assert!(graph.neighbours(from).contains(&Edge(weight, to)));
+        }
+
+        let cost = mst.tree_edges().map(|(_, Edge(w, _))| w).sum::<u32>();
+        assert_eq!(cost, mst.min_cost);
+        assert_eq!(mst.depths()[src], Some(0));
+        assert!(mst.depths().iter().all(|d| d.is_some()));
+    }
+
+    #[test]
+    pub fn prims_heavier_edge_seen_later() {
+        // 1 is pushed through 0 with weight 2, then through 2 with weight 5
+        // before it is settled, it must still hang off 0
+        let adj_list = [
+            vec![Edge(1, 2), Edge(2, 1)],
+            vec![Edge(2, 0), Edge(5, 2)],
+            vec![Edge(1, 0), Edge(5, 1)],
+        ];
+
+        let graph = ListGraph::from(adj_list);
+        let mst_info = prims(graph.clone(), 0).unwrap();
+        assert_eq!(mst_info.predecessors, vec![None, Some(0), Some(0)]);
+        assert_spanning_tree(&graph, &mst_info, 0);
+    }
+
+    #[test]
+    pub fn prims_matches_kruskal() {
+        for seed in 0..20 {
+            let v = 50;
+            let graph = gen_connected_graph(seed, v, 200);
+            let cost = kruskal(graph.clone()).unwrap().cost;
+
+            let lazy = prims(graph.clone(), 0).unwrap();
+            let eager = prims_eager(graph.clone(), 0).unwrap();
+
+            assert_eq!(lazy.min_cost, cost);
+            assert_eq!(eager.min_cost, cost);
+            assert_spanning_tree(&graph, &lazy, 0);
+            assert_spanning_tree(&graph, &eager, 0);
+        }
     }
 }
@@ -0,0 +1,76 @@
+use std::collections::BinaryHeap;
+
+/// Operations the graph algorithms need from a priority queue so that the
+/// queue can be chosen by the caller instead of being hard-wired.
+///
+/// Follows the convention of `BinaryHeap`, `pop` returns the greatest item.
+/// Since [`Edge`](crate::graph::Edge) is ordered by weight in reverse this
+/// means the cheapest edge comes out first.
+pub trait PriorityQueue<T> {
+    fn push(&mut self, item: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    fn is_empty(&self) -> bool;
+}
+
+/// Priority queue over the vertex ids `0..n` where each vertex is queued at
+/// most once and its key can be lowered in place, used by the eager
+/// versions of Djikstra and Prim's.
+pub trait IndexedPriorityQueue<K> {
+    /// Empty queue that can hold the vertices `0..n`
+    fn with_vertices(n: usize) -> Self;
+
+    fn push(&mut self, vertex: usize, key: K);
+
+    /// Lowers the key of a queued `vertex`, `key` must not be larger than
+    /// its current key
+    fn decrease_key(&mut self, vertex: usize, key: K);
+
+    fn contains(&self, vertex: usize) -> bool;
+
+    /// Removes the vertex with the smallest key, returns `(vertex, key)`
+    fn pop_min(&mut self) -> Option<(usize, K)>;
+
+    fn is_empty(&self) -> bool;
+}
+
+impl<T: Ord> PriorityQueue<T> for BinaryHeap<T> {
+    fn push(&mut self, item: T) {
+        BinaryHeap::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BinaryHeap::is_empty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BinaryHeap;
+
+    use crate::graph::Edge;
+
+    use super::PriorityQueue;
+
+    fn drain<Q: PriorityQueue<Edge<u32>>>(mut pq: Q) -> Vec<Edge<u32>> {
+        let mut buf = vec![];
+        while let Some(e) = pq.pop() {
+            buf.push(e);
+        }
+        buf
+    }
+
+    #[test]
+    fn bheap_pops_cheapest_edge() {
+        let mut pq = BinaryHeap::new();
+        for e in [Edge(5, 0), Edge(1, 1), Edge(3, 2)] {
+            PriorityQueue::push(&mut pq, e);
+        }
+        assert_eq!(drain(pq), vec![Edge(1, 1), Edge(3, 2), Edge(5, 0)]);
+    }
+}
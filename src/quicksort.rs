@@ -1,13 +1,28 @@
+use std::cmp::Ordering;
+
 pub struct QuickSort;
 
 impl QuickSort {
-    pub fn partition<T: Ord + Copy>(buf: &mut [T]) -> usize {
+    pub fn partition<T: Ord>(buf: &mut [T]) -> usize {
+        Self::partition_by(buf, &mut T::cmp)
+    }
+
+    /// Partitions around the middle element with `compare`, returns where
+    /// the pivot ends up. An empty slice has no pivot and gives 0.
+    pub fn partition_by<T, F>(buf: &mut [T], compare: &mut F) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if buf.is_empty() {
+            return 0;
+        }
+
         // we use the middle as the pivot element
         // can actl use other places as pivot but middle makes alot of sense
         let mid = buf.len() / 2;
 
-        // we swap the pivot with the first element in the list
-        let pivot = buf[mid];
+        // we swap the pivot with the first element in the list, it stays
+        // at index 0 until the end so it can be compared in place
         buf.swap(0, mid);
 
         // set to the first item in buf
@@ -18,7 +33,7 @@ impl QuickSort {
         // to the left
         // which also makes the right automatically bigger than the pivot
         for i in 1..buf.len() {
-            if buf[i] < pivot {
+            if compare(&buf[i], &buf[0]) == Ordering::Less {
                 // last_small go next first
                 last_small += 1;
                 buf.swap(last_small, i);
@@ -31,11 +46,84 @@ impl QuickSort {
         last_small
     }
 
-    pub fn sort<T: Ord + Copy>(buf: &mut [T]) {
-        if !buf.is_empty() {
-            let pivot_idx = Self::partition(buf);
-            QuickSort::sort(&mut buf[0..pivot_idx]);
-            QuickSort::sort(&mut buf[pivot_idx + 1..]);
+    /// Three way partition around the middle element with `compare`.
+    /// Returns `(lt, gt)` such that `buf[..lt]` is less than the pivot,
+    /// `buf[lt..gt]` is equal to it and `buf[gt..]` is greater. An empty
+    /// slice gives `(0, 0)`.
+    pub fn partition3_by<T, F>(buf: &mut [T], compare: &mut F) -> (usize, usize)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if buf.is_empty() {
+            return (0, 0);
+        }
+
+        // the pivot sits at index 0 until the end like in partition_by
+        let mid = buf.len() / 2;
+        buf.swap(0, mid);
+
+        // buf[1..lt] < pivot, buf[lt..i] == pivot, buf[i..gt] not looked at
+        // yet and buf[gt..] > pivot
+        let (mut lt, mut i, mut gt) = (1, 1, buf.len());
+        while i < gt {
+            match compare(&buf[i], &buf[0]) {
+                Ordering::Less => {
+                    buf.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    gt -= 1;
+                    buf.swap(i, gt);
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+
+        // the pivot goes to the front of the equal run
+        buf.swap(0, lt - 1);
+
+        (lt - 1, gt)
+    }
+
+    pub fn sort<T: Ord>(buf: &mut [T]) {
+        Self::sort_by(buf, T::cmp)
+    }
+
+    pub fn sort_by<T, F>(buf: &mut [T], mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        Self::sort_by_rec(buf, &mut compare)
+    }
+
+    pub fn sort_by_key<T, K, F>(buf: &mut [T], mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        Self::sort_by(buf, |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Elements equal to the pivot are left out of both sides so duplicate
+    /// heavy input does not go quadratic, and only the smaller side is
+    /// recursed into so the depth stays O(lgN).
+    fn sort_by_rec<T, F>(mut buf: &mut [T], compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        while buf.len() > 1 {
+            let (lt, gt) = Self::partition3_by(buf, compare);
+            let (left, rest) = std::mem::take(&mut buf).split_at_mut(lt);
+            let right = &mut rest[gt - lt..];
+
+            if left.len() < right.len() {
+                Self::sort_by_rec(left, compare);
+                buf = right;
+            } else {
+                Self::sort_by_rec(right, compare);
+                buf = left;
+            }
         }
     }
 }
@@ -61,6 +149,7 @@ mod test {
     }
 
     #[test]
+    #[cfg_attr(miri, ignore)]
     fn test_quicksort_random_100k() {
         let mut data = gen_random_array::<100000, _>(None);
         QuickSort::sort(&mut data);
@@ -68,10 +157,45 @@ mod test {
     }
 
     #[test]
+    #[cfg_attr(miri, ignore)]
     fn test_quicksort_random_1mill() {
         let mut data = gen_random_array::<1000000, _>(None);
         QuickSort::sort(&mut data);
         assert_sorted(&data);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_quicksort_all_equal_1mill() {
+        // every element equals the pivot, a two way partition recursed
+        // once per element here and overflowed the stack
+        let mut data = vec![0u32; 1_000_000];
+        QuickSort::sort(&mut data);
+        assert!(data.iter().all(|x| *x == 0));
+
+        let mut data = gen_random_array::<1000000, _>(42069);
+        data.iter_mut().for_each(|x| *x %= 4);
+        QuickSort::sort(&mut data);
+        assert_sorted(&data);
+    }
+
+    #[test]
+    fn test_partition3() {
+        let mut data = vec![3, 1, 2, 5, 2, 0, 2, 4];
+        let (lt, gt) = QuickSort::partition3_by(&mut data, &mut u32::cmp);
+        assert_eq!((lt, gt), (2, 5));
+        assert!(data[..lt].iter().all(|x| *x < 2));
+        assert!(data[lt..gt].iter().all(|x| *x == 2));
+        assert!(data[gt..].iter().all(|x| *x > 2));
+    }
+
+    #[test]
+    fn test_quicksort_by() {
+        let mut data = vec![(3, "c"), (1, "a"), (2, "b"), (1, "d")];
+        QuickSort::sort_by(&mut data, |a, b| b.cmp(a));
+        assert_eq!(data, vec![(3, "c"), (2, "b"), (1, "d"), (1, "a")]);
+
+        QuickSort::sort_by_key(&mut data, |(_, name)| *name);
+        assert_eq!(data, vec![(1, "a"), (2, "b"), (3, "c"), (1, "d")]);
+    }
 }
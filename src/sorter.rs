@@ -0,0 +1,304 @@
+use crate::{
+    heap_sort::HeapSort, insertion_merge::InsertionMergeSort, insertion_sort::InsertionSort,
+    merge_sort::MergeSort, quicksort::QuickSort,
+};
+
+/// Common interface over every sort so they can be benchmarked and tested
+/// in one loop. Parameters such as the threshold of `InsertionMergeSort`
+/// are carried by the value, which also keeps the trait object safe.
+pub trait Sorter<T> {
+    /// Name used for benchmark ids and test failures
+    fn name(&self) -> String;
+
+    fn sort(&self, buf: &mut [T]);
+}
+
+impl<T: Ord> Sorter<T> for MergeSort {
+    fn name(&self) -> String {
+        String::from("merge_sort")
+    }
+
+    fn sort(&self, buf: &mut [T]) {
+        MergeSort::sort(buf)
+    }
+}
+
+impl<T: Ord> Sorter<T> for QuickSort {
+    fn name(&self) -> String {
+        String::from("quick_sort")
+    }
+
+    fn sort(&self, buf: &mut [T]) {
+        QuickSort::sort(buf)
+    }
+}
+
+impl<T: Ord> Sorter<T> for HeapSort {
+    fn name(&self) -> String {
+        String::from("heap_sort")
+    }
+
+    fn sort(&self, buf: &mut [T]) {
+        HeapSort::sort(buf)
+    }
+}
+
+impl<T: Ord> Sorter<T> for InsertionSort {
+    fn name(&self) -> String {
+        String::from("insertion_sort")
+    }
+
+    fn sort(&self, buf: &mut [T]) {
+        InsertionSort::sort(buf)
+    }
+}
+
+impl<T: Ord> Sorter<T> for InsertionMergeSort {
+    fn name(&self) -> String {
+        format!("insertion_merge_sort(s{})", self.s)
+    }
+
+    fn sort(&self, buf: &mut [T]) {
+        InsertionMergeSort::sort(buf, self.s)
+    }
+}
+
+/// Every sort in the crate, `InsertionMergeSort` with a threshold of `s`
+pub fn sorters<T: Ord>(s: usize) -> Vec<Box<dyn Sorter<T>>> {
+    vec![
+        Box::new(MergeSort),
+        Box::new(QuickSort),
+        Box::new(HeapSort),
+        Box::new(InsertionSort),
+        Box::new(InsertionMergeSort::new(s)),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc};
+
+    use nanorand::{Rng, WyRand};
+    use proptest::{
+        collection::vec,
+        prelude::*,
+        test_runner::{Config, TestRunner},
+    };
+
+    use crate::{
+        heap_sort::HeapSort,
+        insertion_merge::InsertionMergeSort,
+        insertion_sort::InsertionSort,
+        merge_sort::MergeSort,
+        quicksort::QuickSort,
+        test_utils::{assert_sorted, gen_random_array},
+    };
+
+    use super::sorters;
+
+    /// Owned record that counts how many times it is dropped, a double drop
+    /// or a leak shows up in the count
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Tracked {
+        key: u32,
+        name: String,
+        drops: Rc<Cell<usize>>,
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    /// Runs `sort` on arrays of up to `max_len` elements and compares each
+    /// result against `slice::sort`, a failure is reported under `name` with
+    /// the smallest input proptest can shrink it to. The values are drawn
+    /// from a tiny range so there are lots of duplicates or from the full
+    /// range, and are left as is, sorted or reversed.
+    fn check_sort<F>(name: &str, sort: F, max_len: usize)
+    where
+        F: Fn(&mut [u32]),
+    {
+        let values = prop_oneof![vec(0..8u32, 0..=max_len), vec(any::<u32>(), 0..=max_len)];
+        let inputs = (values, 0..3).prop_map(|(mut data, order)| {
+            if order > 0 {
+                data.sort();
+            }
+            if order > 1 {
+                data.reverse();
+            }
+            data
+        });
+
+        // miri is orders of magnitude slower, a handful of cases still
+        // covers every code path
+        let cases = if cfg!(miri) { 4 } else { 256 };
+        let mut runner = TestRunner::new(Config {
+            cases,
+            failure_persistence: None,
+            ..Config::default()
+        });
+
+        let result = runner.run(&inputs, |data| {
+            let mut expected = data.clone();
+            expected.sort();
+            let mut buf = data.clone();
+            sort(&mut buf);
+            prop_assert_eq!(buf, expected, "{}: input {:?}", name, data);
+            Ok(())
+        });
+
+        if let Err(e) = result {
+            panic!("{e}");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn every_sorter_sorts() {
+        let data = gen_random_array::<5000, _>(42069);
+        for sorter in sorters(15) {
+            let mut buf = data.clone();
+            sorter.sort(&mut buf);
+            assert_sorted(&buf);
+        }
+    }
+
+    #[test]
+    fn every_sorter_matches_std() {
+        let data = vec![5, 3, 9, 3, 1, 0, 7, 7, 2, 8, 1];
+
+        for sorter in sorters::<i32>(3) {
+            for len in 1..=data.len() {
+                let mut buf = data[..len].to_vec();
+                sorter.sort(&mut buf);
+                let mut expected = data[..len].to_vec();
+                expected.sort();
+                assert_eq!(buf, expected, "{} with {} elements", sorter.name(), len);
+            }
+        }
+    }
+
+    #[test]
+    fn every_sorter_sorts_owned() {
+        let mut rng = WyRand::new_seed(42069);
+        let keys = (0..200).map(|_| rng.generate_range(0..50u32)).collect::<Vec<_>>();
+
+        for sorter in sorters(7) {
+            let drops = Rc::new(Cell::new(0));
+            let mut buf = keys
+                .iter()
+                .map(|k| Tracked {
+                    key: *k,
+                    name: format!("record {}", k),
+                    drops: drops.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            sorter.sort(&mut buf);
+            assert_sorted(&buf);
+            assert!(buf.iter().all(|t| t.name == format!("record {}", t.key)));
+            assert_eq!(drops.get(), 0, "{} dropped an element", sorter.name());
+
+            drop(buf);
+            assert_eq!(drops.get(), keys.len(), "{}", sorter.name());
+        }
+
+        for sorter in sorters(3) {
+            let mut words = ["pear", "fig", "banana", "kiwi", "apple"].map(String::from);
+            sorter.sort(&mut words);
+            assert_eq!(words, ["apple", "banana", "fig", "kiwi", "pear"]);
+        }
+
+        for sorter in sorters(3) {
+            let mut nested = vec![vec![3, 1], vec![], vec![2], vec![1, 5, 9]];
+            sorter.sort(&mut nested);
+            assert_eq!(nested, vec![vec![], vec![1, 5, 9], vec![2], vec![3, 1]]);
+        }
+    }
+
+    #[test]
+    fn every_sorter_matches_slice_sort() {
+        for s in [0, 1, 2, 7, 32] {
+            for sorter in sorters(s) {
+                check_sort(&sorter.name(), |buf| sorter.sort(buf), 64);
+            }
+        }
+    }
+
+    type EntryPoint = (&'static str, Box<dyn Fn(&mut [u32])>);
+
+    #[test]
+    fn every_entry_point_matches_slice_sort() {
+        let desc = |a: &u32, b: &u32| b.cmp(a);
+        let key = |x: &u32| std::cmp::Reverse(*x);
+
+        // sorting descending by comparator or by key and then reversing has
+        // to give the ascending order back
+        let entry_points: Vec<EntryPoint> = vec![
+            ("merge_sort_copying", Box::new(MergeSort::sort_copying)),
+            ("merge_sort_prealloc", Box::new(MergeSort::sort_prealloc)),
+            ("merge_sort_bottom_up", Box::new(MergeSort::sort_bottom_up)),
+            ("merge_sort_by", Box::new(move |buf| {
+                MergeSort::sort_by(buf, desc);
+                buf.reverse();
+            })),
+            ("merge_sort_by_key", Box::new(move |buf| {
+                MergeSort::sort_by_key(buf, key);
+                buf.reverse();
+            })),
+            ("merge_sort_copying_by", Box::new(move |buf| {
+                MergeSort::sort_copying_by(buf, desc);
+                buf.reverse();
+            })),
+            ("merge_sort_prealloc_by", Box::new(move |buf| {
+                MergeSort::sort_prealloc_by(buf, desc);
+                buf.reverse();
+            })),
+            ("merge_sort_bottom_up_by", Box::new(move |buf| {
+                MergeSort::sort_bottom_up_by(buf, desc);
+                buf.reverse();
+            })),
+            ("quick_sort_by", Box::new(move |buf| {
+                QuickSort::sort_by(buf, desc);
+                buf.reverse();
+            })),
+            ("quick_sort_by_key", Box::new(move |buf| {
+                QuickSort::sort_by_key(buf, key);
+                buf.reverse();
+            })),
+            ("heap_sort_by", Box::new(move |buf| {
+                HeapSort::sort_by(buf, desc);
+                buf.reverse();
+            })),
+            ("heap_sort_by_key", Box::new(move |buf| {
+                HeapSort::sort_by_key(buf, key);
+                buf.reverse();
+            })),
+            ("insertion_sort_by", Box::new(move |buf| {
+                InsertionSort::sort_by(buf, desc);
+                buf.reverse();
+            })),
+            ("insertion_sort_by_key", Box::new(move |buf| {
+                InsertionSort::sort_by_key(buf, key);
+                buf.reverse();
+            })),
+            ("insertion_merge_sort_by", Box::new(move |buf| {
+                InsertionMergeSort::sort_by(buf, 0, desc);
+                buf.reverse();
+            })),
+            ("insertion_merge_sort_by_key", Box::new(move |buf| {
+                InsertionMergeSort::sort_by_key(buf, 5, key);
+                buf.reverse();
+            })),
+            ("insertion_merge_sort_key_cmp", Box::new(|buf| {
+                InsertionMergeSort::sort_key_cmp(buf, 3, &mut 0);
+            })),
+        ];
+
+        for (name, sort) in entry_points {
+            check_sort(name, sort, 64);
+        }
+    }
+}
@@ -1,173 +1,620 @@
-use std::marker::PhantomData;
-
-macro_rules! generate_qf_uf_impl {
-    ($($num_type:ident), *) => {
-        $(
-        impl UnionFind<QuickFind, $num_type> {
-            pub fn find(&self, data: $num_type) -> $num_type {
-                self.id[data as usize]
-            }
+use std::{cmp::Ordering, marker::PhantomData, ops::Sub};
 
-            pub fn union(&mut self, p: $num_type, q: $num_type) {
-                // decrement the counter if successful
-                // get representative node for p
-                let representative_p = self.id[p as usize];
-                let representative_q = self.id[q as usize];
-                // change all elements in buffer which is represented by p to be represented by q
-                for item in &mut self.id {
-                    if *item == representative_p {
-                        *item = representative_q;
-                    }
-                }
-                self.count -= 1;
-            }
-            
-            pub fn connected(&self, p: $num_type, q: $num_type) -> bool {
-                self.find(q) == self.find(p)
-            }
+use num_traits::{NumCast, PrimInt, Zero};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnionFind<S, T, RankContainer = PhantomData<()>> {
+    pub id: Vec<T>,
+    pub count: usize,
+    pub rank: RankContainer,
+    phantom: PhantomData<S>,
+}
+
+/// Element of a [`UnionFind`], anything that can be turned into a position
+/// in `id` and back. Every primitive integer already is one, a newtype only
+/// has to implement the two conversions.
+pub trait Index: Copy + PartialEq {
+    fn from_usize(i: usize) -> Self;
+    fn index(self) -> usize;
+}
+
+impl<T: PrimInt> Index for T {
+    fn from_usize(i: usize) -> Self {
+        <T as NumCast>::from(i).expect("index does not fit in the element type")
+    }
+
+    fn index(self) -> usize {
+        self.to_usize().expect("element is not a valid index")
+    }
+}
+
+/// How a [`UnionFind`] finds roots and joins two trees. `R` is whatever the
+/// strategy keeps in `rank`, `PhantomData<()>` if it needs nothing.
+pub trait UnionFindStrategy<T: Index, R> {
+    /// `rank` for `n` singletons
+    fn new_rank(n: usize) -> R;
+
+    /// Makes room in `rank` for `n` more singletons
+    fn grow_rank(rank: &mut R, n: usize);
+
+    /// Root of `i`, only strategies that compress paths change `id`
+    fn find(id: &mut [T], i: T) -> T {
+        walk_to_root(id, i)
+    }
+
+    /// Joins the two different trees rooted at `root_p` and `root_q`
+    fn link(id: &mut [T], rank: &mut R, root_p: T, root_q: T);
+}
+
+/// Strategies that never change `id` in `find`, so `find` and `connected`
+/// only need a shared reference
+pub trait ReadOnlyFind {}
+
+fn walk_to_root<T: Index>(id: &[T], mut i: T) -> T {
+    while i != id[i.index()] {
+        i = id[i.index()]
+    }
+    i
+}
+
+/// Smaller tree goes under the bigger one, `size` holds the size of every
+/// tree
+fn link_by_size<T: Index>(id: &mut [T], size: &mut [T], root_p: T, root_q: T) {
+    let (p, q) = (root_p.index(), root_q.index());
+    let total = T::from_usize(size[p].index() + size[q].index());
+    if size[p].index() < size[q].index() {
+        id[p] = root_q;
+        size[q] = total;
+    } else {
+        id[q] = root_p;
+        size[p] = total;
+    }
+}
+
+impl<S, T, R> UnionFind<S, T, R>
+where
+    S: UnionFindStrategy<T, R>,
+    T: Index,
+{
+    pub fn new(n: T) -> Self {
+        Self {
+            id: (0..n.index()).map(T::from_usize).collect(),
+            phantom: PhantomData,
+            count: n.index(),
+            rank: S::new_rank(n.index()),
         }
-        )*
-    };
+    }
+
+    pub fn union(&mut self, p: T, q: T) {
+        // find root of p and root of q and connect p to q
+        let root_p = S::find(&mut self.id, p);
+        let root_q = S::find(&mut self.id, q);
+
+        if root_p == root_q {
+            return;
+        }
+
+        S::link(&mut self.id, &mut self.rank, root_p, root_q);
+        self.count -= 1;
+    }
+
+    /// Adds `n` new elements, each in a component of its own
+    pub fn grow(&mut self, n: T) {
+        let start = self.id.len();
+        self.id.extend((start..start + n.index()).map(T::from_usize));
+        S::grow_rank(&mut self.rank, n.index());
+        self.count += n.index();
+    }
 }
 
+impl<S: ReadOnlyFind, T: Index, R> UnionFind<S, T, R> {
+    pub fn find(&self, i: T) -> T {
+        walk_to_root(&self.id, i)
+    }
 
-macro_rules! generate_wqu_uf_impl {
-    ($($num_type:ident), *) => {
-        $(
-        impl UnionFind<WeightedQuickUnion, $num_type, Vec<$num_type>> {
-            
-            pub fn new(n: $num_type) -> Self {
-                Self {
-                    id: (0..n).collect(),
-                    phantom: PhantomData::default(),
-                    count: n as usize,
-                    rank: vec![1; n as usize]
-                }
-            }
+    pub fn connected(&self, p: T, q: T) -> bool {
+        self.find(q) == self.find(p)
+    }
+}
 
-            pub fn find(&self, mut i: $num_type) -> $num_type {
-                while i != self.id[i as usize] {
-                    i = self.id[i as usize]
-                }
-                i
-            }
+impl<C: Compression, T: Index> UnionFind<WQupc<C>, T, Vec<T>> {
+    /// Root of `i`, shortening the path to it on the way up
+    pub fn find(&mut self, i: T) -> T {
+        C::find(&mut self.id, i)
+    }
 
-            pub fn union(&mut self, p: $num_type, q: $num_type) {
-                // find root of p and root of q and connect p to q
-                // ie. making the root q the new root of p
-                let root_p = self.find(p);
-                let root_q = self.find(q);
-                
-                if root_p == root_q {
-                    return;
-                }
-
-                if self.rank[root_p as usize] < self.rank[root_q as usize] {
-                    self.id[root_p as usize] = root_q;
-                    self.rank[root_q as usize] += self.rank[root_p as usize];
-                } else {
-                    self.id[root_q as usize] = root_p;
-                    self.rank[root_p as usize] += self.rank[root_q as usize];
-                }
-
-                self.count -= 1;
-            }
+    pub fn connected(&mut self, p: T, q: T) -> bool {
+        self.find(q) == self.find(p)
+    }
+}
 
-            pub fn connected(&self, p: $num_type, q: $num_type) -> bool {
-                self.find(q) == self.find(p)
-            }
+impl<S, T: Index, R> UnionFind<S, T, R> {
+    /// Root of `x` without changing `id`, so it works the same for every
+    /// strategy including the ones that compress paths
+    fn root(&self, x: T) -> T {
+        walk_to_root(&self.id, x)
+    }
+
+    /// Number of links from `x` up to its root
+    pub fn depth(&self, mut x: T) -> usize {
+        let mut depth = 0;
+        while x != self.id[x.index()] {
+            x = self.id[x.index()];
+            depth += 1;
         }
-        )*
-    };
+        depth
+    }
+
+    /// Height of the tallest tree
+    pub fn height(&self) -> usize {
+        (0..self.id.len()).map(|i| self.depth(T::from_usize(i))).max().unwrap_or(0)
+    }
+
+    /// Root of every component in increasing order
+    pub fn roots(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.id.len())
+            .filter(|i| self.id[*i].index() == *i)
+            .map(T::from_usize)
+    }
+
+    /// Every element in the same component as `x` in increasing order
+    ///
+    /// # Time Complexity
+    /// - O(N) root walks
+    pub fn members(&self, x: T) -> impl Iterator<Item = T> + '_ {
+        let root = self.root(x);
+        (0..self.id.len())
+            .map(T::from_usize)
+            .filter(move |i| self.root(*i) == root)
+    }
+
+    /// Component of every element numbered `0..count` in the order the
+    /// components are first seen
+    pub fn component_labels(&self) -> Vec<usize> {
+        let mut label_of_root = vec![None; self.id.len()];
+        let mut next = 0;
+        (0..self.id.len())
+            .map(|i| {
+                let root = self.root(T::from_usize(i)).index();
+                *label_of_root[root].get_or_insert_with(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect()
+    }
 }
 
-macro_rules! generate_qu_uf_impl {
-    ($($num_type:ident), *) => {
-        $(
-        impl UnionFind<QuickUnion, $num_type> {
-            pub fn find(&self, mut i: $num_type) -> $num_type {
-                while i != self.id[i as usize] {
-                    i = self.id[i as usize]
-                }
-                i
-            }
+impl<T: Index> UnionFind<UnionBySize, T, Vec<T>> {
+    /// Number of elements in the same component as `x`
+    pub fn component_size(&self, x: T) -> usize {
+        self.rank[self.find(x).index()].index()
+    }
+}
 
-            pub fn union(&mut self, p: $num_type, q: $num_type) {
-                // find root of p and root of q and connect p to q
-                // ie. making the root q the new root of p
-                let root_p = self.find(p);
-                let root_q = self.find(q);
-                
-                if root_p == root_q {
-                    return;
-                }
-
-                self.count -= 1;
-                self.id[root_p as usize] = root_q;
-            }
+impl<T: Index> UnionFind<UnionByRank, T, Vec<T>> {
+    /// Rank of the root of `x`, an upper bound on the height of its tree
+    pub fn rank(&self, x: T) -> usize {
+        self.rank[self.find(x).index()].index()
+    }
+}
 
-            pub fn connected(&self, p: $num_type, q: $num_type) -> bool {
-                self.find(q) == self.find(p)
+/// `find` returns `id` directly, `union` relabels every element of one
+/// component
+#[derive(Debug)]
+pub struct QuickFind;
+
+impl ReadOnlyFind for QuickFind {}
+
+impl<T: Index> UnionFindStrategy<T, PhantomData<()>> for QuickFind {
+    fn new_rank(_: usize) -> PhantomData<()> {
+        PhantomData
+    }
+
+    fn grow_rank(_: &mut PhantomData<()>, _: usize) {}
+
+    fn find(id: &mut [T], i: T) -> T {
+        id[i.index()]
+    }
+
+    fn link(id: &mut [T], _: &mut PhantomData<()>, root_p: T, root_q: T) {
+        // change all elements in buffer which is represented by p to be represented by q
+        for item in id {
+            if *item == root_p {
+                *item = root_q;
             }
         }
-        )*
-    };
+    }
+}
+
+/// The root of `p` is put under the root of `q`
+#[derive(Debug)]
+pub struct QuickUnion;
+
+impl ReadOnlyFind for QuickUnion {}
+
+impl<T: Index> UnionFindStrategy<T, PhantomData<()>> for QuickUnion {
+    fn new_rank(_: usize) -> PhantomData<()> {
+        PhantomData
+    }
+
+    fn grow_rank(_: &mut PhantomData<()>, _: usize) {}
+
+    fn link(id: &mut [T], _: &mut PhantomData<()>, root_p: T, root_q: T) {
+        id[root_p.index()] = root_q;
+    }
+}
+
+/// Union by size, the smaller tree is put under the bigger one and `rank`
+/// holds the size of every tree
+#[derive(Debug)]
+pub struct UnionBySize;
+
+impl ReadOnlyFind for UnionBySize {}
+
+impl<T: Index> UnionFindStrategy<T, Vec<T>> for UnionBySize {
+    fn new_rank(n: usize) -> Vec<T> {
+        vec![T::from_usize(1); n]
+    }
+
+    fn grow_rank(rank: &mut Vec<T>, n: usize) {
+        rank.resize(rank.len() + n, T::from_usize(1));
+    }
+
+    fn link(id: &mut [T], rank: &mut Vec<T>, root_p: T, root_q: T) {
+        link_by_size(id, rank, root_p, root_q);
+    }
 }
 
-macro_rules! generate_uf_constructor_impl {
-    ($($num_type:ident), *) => {
-        $(
-        impl <A> UnionFind<A, $num_type> {
-            pub fn new(n: $num_type) -> Self {
-                Self {
-                    id: (0..n).collect(),
-                    phantom: PhantomData::default(),
-                    count: n as usize,
-                    rank: PhantomData::default()
-                }
+/// Union by rank, the tree with the smaller rank is put under the other and
+/// `rank` holds an upper bound on the height of every tree
+#[derive(Debug)]
+pub struct UnionByRank;
+
+impl ReadOnlyFind for UnionByRank {}
+
+impl<T: Index> UnionFindStrategy<T, Vec<T>> for UnionByRank {
+    fn new_rank(n: usize) -> Vec<T> {
+        vec![T::from_usize(0); n]
+    }
+
+    fn grow_rank(rank: &mut Vec<T>, n: usize) {
+        rank.resize(rank.len() + n, T::from_usize(0));
+    }
+
+    fn link(id: &mut [T], rank: &mut Vec<T>, root_p: T, root_q: T) {
+        let (p, q) = (root_p.index(), root_q.index());
+
+        // the rank only grows when two trees of equal rank meet
+        match rank[p].index().cmp(&rank[q].index()) {
+            Ordering::Less => id[p] = root_q,
+            Ordering::Greater => id[q] = root_p,
+            Ordering::Equal => {
+                id[q] = root_p;
+                rank[p] = T::from_usize(rank[p].index() + 1);
             }
         }
-        )*
-    };
+    }
 }
 
-generate_uf_constructor_impl!(u8, i8, u16, i16, u32, i32, u64, i64, isize, usize);
-generate_qf_uf_impl!(u8, i8, u16, i16, u32, i32, u64, i64, isize, usize);
-generate_qu_uf_impl!(u8, i8, u16, i16, u32, i32, u64, i64, isize, usize);
-generate_wqu_uf_impl!(u8, i8, u16, i16, u32, i32, u64, i64, isize, usize);
+/// Weighted quick union is union by size
+pub type WeightedQuickUnion = UnionBySize;
 
+/// Weighted quick union with path compression, `C` picks how the path is
+/// shortened on every `find`
 #[derive(Debug)]
-pub struct QuickFind;
+pub struct WQupc<C = FullCompression>(PhantomData<C>);
+
+impl<C: Compression, T: Index> UnionFindStrategy<T, Vec<T>> for WQupc<C> {
+    fn new_rank(n: usize) -> Vec<T> {
+        vec![T::from_usize(1); n]
+    }
+
+    fn grow_rank(rank: &mut Vec<T>, n: usize) {
+        rank.resize(rank.len() + n, T::from_usize(1));
+    }
+
+    fn find(id: &mut [T], i: T) -> T {
+        C::find(id, i)
+    }
+
+    fn link(id: &mut [T], rank: &mut Vec<T>, root_p: T, root_q: T) {
+        link_by_size(id, rank, root_p, root_q);
+    }
+}
 
+/// Every vertex on the path is pointed straight at the root, needs a second
+/// pass over the path
 #[derive(Debug)]
-pub struct QuickUnion;
+pub struct FullCompression;
 
+/// Every other vertex on the path is pointed at its grandparent, one pass
 #[derive(Debug)]
-pub struct WeightedQuickUnion;
+pub struct PathHalving;
 
-/// Weighted quick union with path compression
+/// Every vertex on the path is pointed at its grandparent, one pass
 #[derive(Debug)]
-pub struct WQupc;
+pub struct PathSplitting;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct UnionFind<A, T, RankContainer = PhantomData<()>> {
-    pub id: Vec<T>,
+/// How `WQupc` shortens the path from `i` to its root during `find`
+pub trait Compression {
+    fn find<T: Index>(id: &mut [T], i: T) -> T;
+}
+
+impl Compression for FullCompression {
+    fn find<T: Index>(id: &mut [T], mut i: T) -> T {
+        let root = walk_to_root(id, i);
+
+        while i != root {
+            let next = id[i.index()];
+            id[i.index()] = root;
+            i = next;
+        }
+
+        root
+    }
+}
+
+impl Compression for PathHalving {
+    fn find<T: Index>(id: &mut [T], mut i: T) -> T {
+        while i != id[i.index()] {
+            id[i.index()] = id[id[i.index()].index()];
+            i = id[i.index()];
+        }
+
+        i
+    }
+}
+
+impl Compression for PathSplitting {
+    fn find<T: Index>(id: &mut [T], mut i: T) -> T {
+        while i != id[i.index()] {
+            let next = id[i.index()];
+            id[i.index()] = id[next.index()];
+            i = next;
+        }
+
+        i
+    }
+}
+
+/// Point in the history of a [`RollbackUnionFind`] that it can be rolled
+/// back to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snapshot {
+    len: usize,
+    /// stamp of the newest union at the time, tells a snapshot apart from
+    /// one of the same length taken after a rollback
+    last: Option<u64>,
+}
+
+/// Union by rank without path compression that can undo its unions in LIFO
+/// order.
+///
+/// `find` never changes `id`, so every successful `union` only touches one
+/// parent and maybe one rank and both are recorded on a stack.
+///
+/// # Time Complexity
+/// - `find`, `union` and `connected` are O(lgN)
+/// - `rollback` is O(1) per undone union
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollbackUnionFind {
+    pub id: Vec<usize>,
     pub count: usize,
-    pub rank: RankContainer,
-    phantom: PhantomData<A>,
+    pub rank: Vec<usize>,
+    /// `(child, rank_increased, stamp)` of every union that merged two
+    /// trees, the parent of `child` at that time is still `id[child]` when
+    /// undoing it
+    history: Vec<(usize, bool, u64)>,
+    /// number of unions ever recorded, never goes down so every union gets
+    /// a distinct stamp
+    stamps: u64,
+}
+
+impl RollbackUnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            id: (0..n).collect(),
+            count: n,
+            rank: vec![0; n],
+            history: vec![],
+            stamps: 0,
+        }
+    }
+
+    pub fn find(&self, mut i: usize) -> usize {
+        while i != self.id[i] {
+            i = self.id[i]
+        }
+        i
+    }
+
+    /// Returns whether `p` and `q` were in different trees, a union that
+    /// does nothing is not recorded
+    pub fn union(&mut self, p: usize, q: usize) -> bool {
+        let root_p = self.find(p);
+        let root_q = self.find(q);
+
+        if root_p == root_q {
+            return false;
+        }
+
+        let (child, parent) = match self.rank[root_p].cmp(&self.rank[root_q]) {
+            Ordering::Less => (root_p, root_q),
+            _ => (root_q, root_p),
+        };
+
+        let rank_increased = self.rank[child] == self.rank[parent];
+        self.id[child] = parent;
+        if rank_increased {
+            self.rank[parent] += 1;
+        }
+
+        self.history.push((child, rank_increased, self.stamps));
+        self.stamps += 1;
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&self, p: usize, q: usize) -> bool {
+        self.find(q) == self.find(p)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            len: self.history.len(),
+            last: self.history.last().map(|(_, _, stamp)| *stamp),
+        }
+    }
+
+    /// Undoes every union made after `snapshot` was taken
+    ///
+    /// # Panics
+    /// If `snapshot` was taken after unions that have already been rolled
+    /// back
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        // the newest union when the snapshot was taken has to still be at the
+        // same place in the history
+        let last = snapshot.len.checked_sub(1).and_then(|i| self.history.get(i));
+        assert!(
+            snapshot.len <= self.history.len() && last.map(|e| e.2) == snapshot.last,
+            "snapshot is from a rolled back state"
+        );
+
+        while self.history.len() > snapshot.len {
+            let (child, rank_increased, _) = self.history.pop().unwrap();
+            let parent = self.id[child];
+            self.id[child] = child;
+            if rank_increased {
+                self.rank[parent] -= 1;
+            }
+            self.count += 1;
+        }
+    }
+}
+
+/// A `union` that disagrees with the constraints already added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction<W> {
+    /// `x_q - x_p` implied by the earlier constraints
+    pub expected: W,
+    /// `x_q - x_p` asked for by the rejected `union`
+    pub found: W,
+}
+
+/// Union-find where every element `x` has an unknown value and `union` adds
+/// the constraint `x_q - x_p = w`.
+///
+/// Each element stores its offset to its parent, so the offset to the root
+/// is the sum along the path. Union by size with full path compression, the
+/// offsets are folded together while the path is compressed.
+///
+/// # Time Complexity
+/// - `find`, `union` and `diff` are O(lg*N) amortized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PotentialUnionFind<W> {
+    pub id: Vec<usize>,
+    pub count: usize,
+    pub size: Vec<usize>,
+    /// `offset[i]` is `x_i - x_id[i]`
+    pub offset: Vec<W>,
+}
+
+impl<W> PotentialUnionFind<W>
+where
+    W: Copy + PartialEq + Zero + Sub<Output = W>,
+{
+    pub fn new(n: usize) -> Self {
+        Self {
+            id: (0..n).collect(),
+            count: n,
+            size: vec![1; n],
+            offset: vec![W::zero(); n],
+        }
+    }
+
+    /// Root of `i` and `x_i - x_root`
+    pub fn find(&mut self, i: usize) -> (usize, W) {
+        let mut path = vec![];
+        let mut root = i;
+        while root != self.id[root] {
+            path.push(root);
+            root = self.id[root];
+        }
+
+        // walk back down from the vertex nearest the root, every offset on
+        // the path becomes an offset to the root
+        let mut acc = W::zero();
+        for &v in path.iter().rev() {
+            acc = acc + self.offset[v];
+            self.offset[v] = acc;
+            self.id[v] = root;
+        }
+
+        (root, self.offset[i])
+    }
+
+    /// Adds `x_q - x_p = w`. Returns whether `p` and `q` were in different
+    /// components, or the contradiction if they were already connected with
+    /// a different difference, in which case nothing is changed.
+    pub fn union(&mut self, p: usize, q: usize, w: W) -> Result<bool, Contradiction<W>> {
+        let (root_p, offset_p) = self.find(p);
+        let (root_q, offset_q) = self.find(q);
+
+        if root_p == root_q {
+            let expected = offset_q - offset_p;
+            if expected != w {
+                return Err(Contradiction { expected, found: w });
+            }
+            return Ok(false);
+        }
+
+        // x_root_q - x_root_p = (x_p - x_root_p) + w - (x_q - x_root_q)
+        if self.size[root_p] < self.size[root_q] {
+            self.id[root_p] = root_q;
+            self.offset[root_p] = offset_q - w - offset_p;
+            self.size[root_q] += self.size[root_p];
+        } else {
+            self.id[root_q] = root_p;
+            self.offset[root_q] = offset_p + w - offset_q;
+            self.size[root_p] += self.size[root_q];
+        }
+
+        self.count -= 1;
+        Ok(true)
+    }
+
+    pub fn connected(&mut self, p: usize, q: usize) -> bool {
+        self.find(q).0 == self.find(p).0
+    }
+
+    /// `x_q - x_p`, `None` if nothing relates the two
+    pub fn diff(&mut self, p: usize, q: usize) -> Option<W> {
+        let (root_p, offset_p) = self.find(p);
+        let (root_q, offset_q) = self.find(q);
+        (root_p == root_q).then(|| offset_q - offset_p)
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use nanorand::{Rng, WyRand};
+
     use crate::union_find::QuickFind;
 
-    use super::{QuickUnion, UnionFind, WeightedQuickUnion};
+    use super::{
+        Compression, Contradiction, FullCompression, Index, PathHalving, PathSplitting,
+        PotentialUnionFind, QuickUnion, RollbackUnionFind, UnionByRank, UnionBySize, UnionFind,
+        WQupc, WeightedQuickUnion,
+    };
 
     #[test]
     fn ui_test() {
-        let uf = UnionFind::<QuickUnion, i32>::new(10);
-        let uf = UnionFind::<QuickFind, i32>::new(10);
-        let uf = UnionFind::<WeightedQuickUnion, i32, Vec<i32>>::new(10);
+        let _uf = UnionFind::<QuickUnion, i32>::new(10);
+        let _uf = UnionFind::<QuickFind, i32>::new(10);
+        let _uf = UnionFind::<WeightedQuickUnion, i32, Vec<i32>>::new(10);
+        let _uf = UnionFind::<WQupc, i32, Vec<i32>>::new(10);
+        let _uf = UnionFind::<WQupc<PathHalving>, u8, Vec<u8>>::new(10);
     }
 
     #[test]
@@ -209,4 +656,322 @@ mod test {
         uf.union(5, 0);
         assert_eq!(vec![6,2,2,4,4,6,6,7,4,4], uf.id);
     }
+
+    #[test]
+    fn wqupc_full_compression() {
+        let mut uf = UnionFind::<WQupc, usize, Vec<usize>>::new(10);
+        uf.union(4, 3);
+        uf.union(3, 8);
+        uf.union(6, 5);
+        uf.union(9, 4);
+        uf.union(2, 1);
+        uf.union(5, 0);
+        uf.union(7, 2);
+        uf.union(6, 1);
+        uf.union(7, 3);
+
+        // every vertex on the path from 8 now points at the root
+        let root = uf.find(8);
+        assert_eq!(uf.count, 1);
+        assert!((0..10).all(|i| uf.id[i] == root || uf.id[uf.id[i]] == root));
+        uf.find(0);
+        assert_eq!(uf.id[0], root);
+    }
+
+    /// runs the same random unions on `WeightedQuickUnion` and `WQupc<C>`
+    fn matches_weighted_quick_union<C: Compression>() {
+        let n = 500;
+        let mut rng = WyRand::new_seed(42069);
+        let mut wqu = UnionFind::<WeightedQuickUnion, usize, Vec<usize>>::new(n);
+        let mut uf = UnionFind::<WQupc<C>, usize, Vec<usize>>::new(n);
+
+        for _ in 0..2 * n {
+            let (p, q) = (rng.generate_range(0..n), rng.generate_range(0..n));
+            if rng.generate_range(0..2u8) == 0 {
+                wqu.union(p, q);
+                uf.union(p, q);
+            } else {
+                assert_eq!(wqu.connected(p, q), uf.connected(p, q));
+            }
+            assert_eq!(wqu.count, uf.count);
+        }
+    }
+
+    #[test]
+    fn wqupc_variants() {
+        matches_weighted_quick_union::<FullCompression>();
+        matches_weighted_quick_union::<PathHalving>();
+        matches_weighted_quick_union::<PathSplitting>();
+    }
+
+    #[test]
+    fn union_by_size_and_rank() {
+        let mut by_size = UnionFind::<UnionBySize, usize, Vec<usize>>::new(10);
+        let mut by_rank = UnionFind::<UnionByRank, usize, Vec<usize>>::new(10);
+        for (p, q) in [(4, 3), (3, 8), (6, 5), (9, 4), (2, 1), (5, 0), (7, 2)] {
+            by_size.union(p, q);
+            by_rank.union(p, q);
+        }
+
+        assert_eq!(by_size.component_size(8), 4);
+        assert_eq!(by_size.component_size(0), 3);
+        assert_eq!(by_size.component_size(1), 3);
+
+        // {4, 3, 8, 9} was built from two rank 0 trees and then two more
+        // singletons, so it only has rank 1
+        assert_eq!(by_rank.rank(8), 1);
+        assert_eq!(by_rank.rank(7), 1);
+        assert_eq!(by_rank.count, 3);
+        assert!(by_rank.connected(9, 8));
+        assert!(!by_rank.connected(9, 7));
+    }
+
+    #[test]
+    fn height_is_logarithmic() {
+        // merging equal halves is the worst case for both strategies
+        let n = 1 << 10;
+        let mut by_size = UnionFind::<UnionBySize, usize, Vec<usize>>::new(n);
+        let mut by_rank = UnionFind::<UnionByRank, usize, Vec<usize>>::new(n);
+        let mut width = 1;
+        while width < n {
+            for i in (0..n).step_by(2 * width) {
+                by_size.union(i, i + width);
+                by_rank.union(i, i + width);
+            }
+            width *= 2;
+        }
+
+        assert_eq!(by_size.component_size(0), n);
+        assert_eq!(by_size.height(), 10);
+        assert_eq!(by_rank.height(), 10);
+        assert_eq!(by_rank.rank(0), 10);
+    }
+
+    #[test]
+    fn rollback() {
+        let mut uf = RollbackUnionFind::new(6);
+        uf.union(0, 1);
+        let before = uf.snapshot();
+        let state = uf.clone();
+
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+        let middle = uf.snapshot();
+        assert!(uf.union(4, 5));
+        assert_eq!(uf.count, 2);
+
+        uf.rollback(middle);
+        assert!(!uf.connected(4, 5));
+        assert!(uf.connected(0, 3));
+
+        uf.rollback(before);
+        assert_eq!((&uf.id, &uf.rank, uf.count), (&state.id, &state.rank, state.count));
+        assert!(!uf.connected(1, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot is from a rolled back state")]
+    fn rollback_stale_snapshot() {
+        let mut uf = RollbackUnionFind::new(4);
+        let before = uf.snapshot();
+        uf.union(0, 1);
+        let stale = uf.snapshot();
+        uf.rollback(before);
+
+        // refills the history to the same length with a different union
+        uf.union(2, 3);
+        uf.rollback(stale);
+    }
+
+    #[test]
+    fn rollback_matches_rebuild() {
+        // after undoing any suffix of the unions the structure must be
+        // exactly the one built from the remaining prefix
+        let n = 200;
+        let mut rng = WyRand::new_seed(42069);
+        let pairs = (0..300)
+            .map(|_| (rng.generate_range(0..n), rng.generate_range(0..n)))
+            .collect::<Vec<_>>();
+
+        let mut uf = RollbackUnionFind::new(n);
+        let mut snapshots = vec![];
+        for &(p, q) in &pairs {
+            snapshots.push(uf.snapshot());
+            uf.union(p, q);
+        }
+
+        for (i, snapshot) in snapshots.into_iter().enumerate().rev() {
+            uf.rollback(snapshot);
+            let mut rebuilt = RollbackUnionFind::new(n);
+            for &(p, q) in &pairs[..i] {
+                rebuilt.union(p, q);
+            }
+            assert_eq!(uf.id, rebuilt.id);
+            assert_eq!(uf.rank, rebuilt.rank);
+            assert_eq!(uf.count, rebuilt.count);
+        }
+    }
+
+    #[test]
+    fn potential_constraints() {
+        // x1 - x0 = 3, x2 - x1 = 4, x2 - x3 = 10
+        let mut uf = PotentialUnionFind::<i64>::new(5);
+        assert_eq!(uf.union(0, 1, 3), Ok(true));
+        assert_eq!(uf.union(1, 2, 4), Ok(true));
+        assert_eq!(uf.union(3, 2, 10), Ok(true));
+
+        assert_eq!(uf.diff(0, 2), Some(7));
+        assert_eq!(uf.diff(2, 0), Some(-7));
+        assert_eq!(uf.diff(3, 0), Some(3));
+        assert_eq!(uf.diff(0, 4), None);
+
+        // already implied, and one that is not
+        assert_eq!(uf.union(3, 1, 6), Ok(false));
+        assert_eq!(uf.union(0, 3, 0), Err(Contradiction { expected: -3, found: 0 }));
+        assert_eq!(uf.count, 2);
+        assert_eq!(uf.diff(0, 3), Some(-3));
+    }
+
+    #[test]
+    fn potential_matches_values() {
+        // every constraint is taken from hidden values so none contradict,
+        // and every difference found must match them
+        let n = 300;
+        let mut rng = WyRand::new_seed(42069);
+        let values = (0..n)
+            .map(|_| rng.generate_range(0..10_000i64) - 5_000)
+            .collect::<Vec<_>>();
+        let mut uf = PotentialUnionFind::new(n);
+        let mut plain = UnionFind::<WeightedQuickUnion, usize, Vec<usize>>::new(n);
+
+        for _ in 0..n {
+            let (p, q) = (rng.generate_range(0..n), rng.generate_range(0..n));
+            assert_eq!(uf.union(p, q, values[q] - values[p]), Ok(!plain.connected(p, q)));
+            plain.union(p, q);
+
+            let (a, b) = (rng.generate_range(0..n), rng.generate_range(0..n));
+            let expected = plain.connected(a, b).then(|| values[b] - values[a]);
+            assert_eq!(uf.diff(a, b), expected);
+        }
+
+        // an off by one constraint between connected elements is caught
+        let (p, q) = (0..n)
+            .flat_map(|p| (0..n).map(move |q| (p, q)))
+            .find(|&(p, q)| p != q && plain.connected(p, q))
+            .unwrap();
+        assert!(uf.union(p, q, values[q] - values[p] + 1).is_err());
+    }
+
+    #[test]
+    fn enumerations() {
+        let mut uf = UnionFind::<WeightedQuickUnion, usize, Vec<usize>>::new(8);
+        for (p, q) in [(4, 3), (3, 7), (6, 5), (1, 0)] {
+            uf.union(p, q);
+        }
+
+        assert_eq!(uf.roots().count(), uf.count);
+        assert_eq!(uf.members(7).collect::<Vec<_>>(), vec![3, 4, 7]);
+        assert_eq!(uf.members(2).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(uf.component_labels(), vec![0, 0, 1, 2, 2, 3, 3, 2]);
+
+        uf.grow(2);
+        assert_eq!(uf.count, 6);
+        assert_eq!(uf.component_size(9), 1);
+        uf.union(9, 2);
+        assert_eq!(uf.component_labels(), vec![0, 0, 1, 2, 2, 3, 3, 2, 4, 1]);
+    }
+
+    /// labels from every strategy after the same unions are compared
+    /// exactly, which holds since component_labels numbers components in
+    /// order of first occurrence regardless of which element is the root
+    #[test]
+    fn enumerations_all_strategies() {
+        let n = 200u32;
+        let mut rng = WyRand::new_seed(42069);
+        let pairs = (0..150)
+            .map(|_| (rng.generate_range(0..n), rng.generate_range(0..n)))
+            .collect::<Vec<_>>();
+
+        let mut qf = UnionFind::<QuickFind, u32>::new(n / 2);
+        let mut qu = UnionFind::<QuickUnion, u32>::new(n / 2);
+        let mut by_size = UnionFind::<UnionBySize, u32, Vec<u32>>::new(n / 2);
+        let mut by_rank = UnionFind::<UnionByRank, u32, Vec<u32>>::new(n / 2);
+        let mut wqupc = UnionFind::<WQupc<PathHalving>, u32, Vec<u32>>::new(n / 2);
+        qf.grow(n / 2);
+        qu.grow(n / 2);
+        by_size.grow(n / 2);
+        by_rank.grow(n / 2);
+        wqupc.grow(n / 2);
+
+        for &(p, q) in &pairs {
+            qf.union(p, q);
+            qu.union(p, q);
+            by_size.union(p, q);
+            by_rank.union(p, q);
+            wqupc.union(p, q);
+        }
+
+        let labels = qf.component_labels();
+        assert_eq!(labels.iter().max().map(|l| l + 1), Some(qf.count));
+        for other in [
+            qu.component_labels(),
+            by_size.component_labels(),
+            by_rank.component_labels(),
+            wqupc.component_labels(),
+        ] {
+            assert_eq!(other, labels);
+        }
+
+        for x in [0, 57, n - 1] {
+            let members = by_rank.members(x).collect::<Vec<_>>();
+            assert_eq!(members.len(), by_size.component_size(x));
+            assert_eq!(wqupc.members(x).collect::<Vec<_>>(), members);
+            assert!(members.iter().all(|m| labels[*m as usize] == labels[x as usize]));
+        }
+        assert_eq!(by_size.roots().count(), by_size.count);
+        assert_eq!(wqupc.roots().count(), wqupc.count);
+    }
+
+    #[test]
+    fn quick_find_union_connected() {
+        // a union of two connected elements does not change the count
+        let mut qf = UnionFind::<QuickFind, u8>::new(4);
+        qf.union(0, 1);
+        qf.union(1, 0);
+        assert_eq!(qf.count, 3);
+        assert_eq!(qf.id, vec![1, 1, 2, 3]);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Vertex(u32);
+
+    impl Index for Vertex {
+        fn from_usize(i: usize) -> Self {
+            Vertex(i as u32)
+        }
+
+        fn index(self) -> usize {
+            self.0 as usize
+        }
+    }
+
+    #[test]
+    fn newtype_index() {
+        let mut qu = UnionFind::<QuickUnion, Vertex>::new(Vertex(5));
+        let mut wqu = UnionFind::<WeightedQuickUnion, Vertex, Vec<Vertex>>::new(Vertex(5));
+        let mut wqupc = UnionFind::<WQupc<PathSplitting>, Vertex, Vec<Vertex>>::new(Vertex(5));
+        for (p, q) in [(0, 1), (2, 3), (1, 3)] {
+            qu.union(Vertex(p), Vertex(q));
+            wqu.union(Vertex(p), Vertex(q));
+            wqupc.union(Vertex(p), Vertex(q));
+        }
+
+        assert!(qu.connected(Vertex(0), Vertex(2)));
+        assert!(wqupc.connected(Vertex(0), Vertex(2)));
+        assert!(!wqu.connected(Vertex(4), Vertex(2)));
+        assert_eq!(wqu.component_size(Vertex(3)), 4);
+        assert_eq!(wqu.members(Vertex(4)).collect::<Vec<_>>(), vec![Vertex(4)]);
+        assert_eq!(qu.component_labels(), vec![0, 0, 0, 0, 1]);
+    }
 }